@@ -59,3 +59,22 @@ func test_boxes_spaced():
 	n.offset = Vector3.ONE * 1.1
 	add_child(n)
 	return n
+
+
+# Compare these two against a build without the set_transform changes to see the gain of the
+# cached scaled shapes. The step times of each test are written to user://<engine>_<test>.txt
+func test_boxes_kinematic():
+	var n := boxes_scene.instance()
+	n.offset = Vector3.ONE * 1.1
+	n.kinematic = true
+	add_child(n)
+	return n
+
+
+func test_boxes_kinematic_scaled():
+	var n := boxes_scene.instance()
+	n.offset = Vector3.ONE * 1.1
+	n.kinematic = true
+	n.scaled = true
+	add_child(n)
+	return n
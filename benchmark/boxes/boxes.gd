@@ -8,10 +8,26 @@ export var origin := Vector3.ONE
 export var offset := Vector3.ONE
 export var box_godot: PackedScene
 export var colors := PoolColorArray([Color.white, Color.red, Color.green, Color.blue, Color.yellow, Color.magenta])
+# Move all boxes every physics frame by setting their transform, like animated platforms
+export var kinematic := false
+# Also cycle the scale of the kinematic boxes through a few values. The boxes get a convex shape, as
+# only convex, concave and heightmap shapes are rebuilt to scale them.
+export var scaled := false
+export var scale_steps := 4
+
+var time := 0.0
+var frame := 0
 
 
 func _ready():
 	BatchedMeshManager.enable_culling = false # Should reduce rendering impact a little more
+	var convex: ConvexPolygonShape
+	if scaled:
+		convex = ConvexPolygonShape.new()
+		var points := PoolVector3Array()
+		for corner in 8:
+			points.push_back(Vector3(corner & 1, (corner >> 1) & 1, (corner >> 2) & 1) - Vector3.ONE * 0.5)
+		convex.points = points
 	var i := 0
 	for a in x:
 		for b in y:
@@ -20,4 +36,22 @@ func _ready():
 				n.get_node("Mesh").color = colors[i % len(colors)]
 				i += 1
 				n.translation = Vector3(a, b, c) * offset + origin
+				if kinematic:
+					n.mode = RigidBody.MODE_KINEMATIC
+				if scaled:
+					n.get_node("Shape").shape = convex
 				add_child(n)
+
+
+func _physics_process(delta):
+	if not kinematic:
+		return
+	time += delta
+	frame += 1
+	var motion := Vector3(0, sin(time) * delta, 0)
+	var scale := Vector3.ONE * (1.0 - 0.05 * (frame % scale_steps))
+	for n in get_children():
+		if n is RigidBody:
+			n.translation += motion
+			if scaled:
+				n.scale = scale
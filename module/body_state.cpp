@@ -3,6 +3,13 @@
 #include "server.h"
 
 
+void PluggablePhysicsDirectBodyState::_bind_methods() {
+	ClassDB::bind_method(D_METHOD("get_contact_depth", "contact_idx"), &PluggablePhysicsDirectBodyState::get_contact_depth);
+	ClassDB::bind_method(D_METHOD("get_contact_relative_velocity", "contact_idx"), &PluggablePhysicsDirectBodyState::get_contact_relative_velocity);
+	ClassDB::bind_method(D_METHOD("get_contact_collider_user_flags", "contact_idx"), &PluggablePhysicsDirectBodyState::get_contact_collider_user_flags);
+	ClassDB::bind_method(D_METHOD("get_dominance_group"), &PluggablePhysicsDirectBodyState::get_dominance_group);
+}
+
 Vector3 PluggablePhysicsDirectBodyState::get_total_gravity() const {
 	return this->state.gravity;
 }
@@ -98,6 +105,10 @@ bool PluggablePhysicsDirectBodyState::is_sleeping() const {
 	return this->state.sleeping;
 };
 
+int PluggablePhysicsDirectBodyState::get_dominance_group() const {
+	return this->state.dominance_group;
+};
+
 int PluggablePhysicsDirectBodyState::get_contact_count() const {
 	return this->state.contact_count;
 }
@@ -114,6 +125,14 @@ float PluggablePhysicsDirectBodyState::get_contact_impulse(int id) const {
 	return this->_select_contact(id)->impulse;
 }
 
+float PluggablePhysicsDirectBodyState::get_contact_depth(int id) const {
+	return this->_select_contact(id)->depth;
+}
+
+Vector3 PluggablePhysicsDirectBodyState::get_contact_relative_velocity(int id) const {
+	return this->_select_contact(id)->relative_velocity;
+}
+
 int PluggablePhysicsDirectBodyState::get_contact_local_shape(int id) const {
 	return this->_select_contact(id)->local_shape;
 };
@@ -143,6 +162,10 @@ Vector3 PluggablePhysicsDirectBodyState::get_contact_collider_velocity_at_positi
 	return this->_select_contact(id)->velocity;
 };
 
+uint32_t PluggablePhysicsDirectBodyState::get_contact_collider_user_flags(int id) const {
+	return this->_select_contact(id)->user_flags;
+};
+
 real_t PluggablePhysicsDirectBodyState::get_step() const {
 	return this->delta;
 };
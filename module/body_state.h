@@ -27,6 +27,9 @@ class PluggablePhysicsDirectBodyState : public PhysicsDirectBodyState {
 
 	_FORCE_INLINE_ const struct physics_body_contact *_select_contact(int id) const;
 
+protected:
+	static void _bind_methods();
+
 public:
 	virtual Vector3 get_total_gravity() const;
 	virtual float get_total_angular_damp() const;
@@ -57,11 +60,15 @@ public:
 	virtual void set_sleep_state(bool p_enable);
 	virtual bool is_sleeping() const;
 
+	int get_dominance_group() const;
+
 	virtual int get_contact_count() const;
 
 	virtual Vector3 get_contact_local_position(int p_contact_idx) const;
 	virtual Vector3 get_contact_local_normal(int p_contact_idx) const;
 	virtual float get_contact_impulse(int p_contact_idx) const;
+	float get_contact_depth(int p_contact_idx) const;
+	Vector3 get_contact_relative_velocity(int p_contact_idx) const;
 	virtual int get_contact_local_shape(int p_contact_idx) const;
 
 	virtual RID get_contact_collider(int p_contact_idx) const;
@@ -70,6 +77,7 @@ public:
 	virtual Object *get_contact_collider_object(int p_contact_idx) const;
 	virtual int get_contact_collider_shape(int p_contact_idx) const;
 	virtual Vector3 get_contact_collider_velocity_at_position(int p_contact_idx) const;
+	uint32_t get_contact_collider_user_flags(int p_contact_idx) const;
 
 	virtual real_t get_step() const;
 	virtual void integrate_forces();
@@ -88,6 +88,14 @@ API_CUSTOM_FUNCTIONS = {
         ('index_t', 'area'),
         ('struct physics_area_monitor_event *', 'event')
     ]),
+    'area_set_monitor_callback': ('void', [
+        ('index_t', 'area'),
+        ('bool', 'enable')
+    ]),
+    'area_set_area_monitor_callback': ('void', [
+        ('index_t', 'area'),
+        ('bool', 'enable')
+    ]),
     'body_get_contact': ('void', [
         ('index_t', 'body'),
         ('uint32_t', 'id'),
@@ -97,7 +105,7 @@ API_CUSTOM_FUNCTIONS = {
         ('index_t', 'body'),
         ('struct physics_body_state *', 'state')
     ]),
-    'body_get_collision_exception': ('index_t', [
+    'body_get_collision_exception': ('maybe_index_t', [
         ('index_t', 'body'),
         ('int', 'index')
     ]),
@@ -162,6 +170,7 @@ API_STRUCTS = {
         ('real_t', 'angular_damp'),
         ('real_t', 'linear_damp'),
         ('bool', 'sleeping'),
+        ('int8_t', 'dominance_group'),
     ],
     'physics_body_contact': [
         ('godot_vector3', 'position'),
@@ -172,7 +181,10 @@ API_STRUCTS = {
         ('uint32_t', 'shape'),
         ('uint32_t', 'local_shape'),
         ('int', 'object_id'),
+        ('godot_vector3', 'relative_velocity'),
         ('float', 'impulse'),
+        ('float', 'depth'),
+        ('uint32_t', 'user_flags'),
     ],
     'physics_ray_info': [
         ('godot_vector3', 'from'),
@@ -190,6 +202,7 @@ API_STRUCTS = {
         ('index_t', 'id'),
         ('int', 'object_id'),
         ('int', 'shape'),
+        ('uint32_t', 'user_flags'),
     ],
     'physics_shape_info': [
         ('index_t', 'shape'),
@@ -209,6 +222,8 @@ API_STRUCTS = {
     'physics_area_monitor_event': [
         ('index_t', 'id'),
         ('int', 'object_id'),
+        ('uint32_t', 'body_shape'),
+        ('uint32_t', 'area_shape'),
         ('bool', 'added'),
     ],
     'physics_server': [],
@@ -221,6 +236,7 @@ VALIDATE_ALL_RIDS = {
     'body_add_collision_exception',
     'body_remove_collision_exception',
     'body_add_shape',
+    'body_set_shape',
     'joint_create_cone_twist',
     'joint_create_generic_6dof',
     'joint_create_hinge',
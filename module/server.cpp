@@ -30,21 +30,43 @@ void PluggablePhysicsServer::_bind_methods() {
 }
 
 void PluggablePhysicsServer::area_set_monitor_callback(RID area, Object *receiver, const StringName &method) {
+	ERR_FAIL_COND_MSG(this->fn_table.area_set_monitor_callback == nullptr, "Not implemented");
 	index_t id = this->get_index(area);
 	ERR_FAIL_COND_MSG(id == 0, "Invalid RID");
-	AreaCallback callback(receiver, method);
-	this->area_body_monitor_callbacks.set(id, callback);
+	if (receiver == nullptr) {
+		this->area_body_monitor_callbacks.erase(id);
+	} else {
+		AreaCallback callback(receiver, method);
+		this->area_body_monitor_callbacks.set(id, callback);
+	}
+	(*this->fn_table.area_set_monitor_callback)(id, receiver != nullptr);
 }
 
 void PluggablePhysicsServer::area_set_area_monitor_callback(RID area, Object *receiver, const StringName &method) {
+	ERR_FAIL_COND_MSG(this->fn_table.area_set_area_monitor_callback == nullptr, "Not implemented");
 	index_t id = this->get_index(area);
 	ERR_FAIL_COND_MSG(id == 0, "Invalid RID");
-	AreaCallback callback(receiver, method);
-	this->area_area_monitor_callbacks.set(id, callback);
+	if (receiver == nullptr) {
+		this->area_area_monitor_callbacks.erase(id);
+	} else {
+		AreaCallback callback(receiver, method);
+		this->area_area_monitor_callbacks.set(id, callback);
+	}
+	(*this->fn_table.area_set_area_monitor_callback)(id, receiver != nullptr);
 }
 
 void PluggablePhysicsServer::body_get_collision_exceptions(RID body, List<RID> *list) {
-	ERR_FAIL_MSG("TODO");
+	ERR_FAIL_COND_MSG(this->fn_table.body_get_collision_exception_count == nullptr, "Not implemented");
+	ERR_FAIL_COND_MSG(this->fn_table.body_get_collision_exception == nullptr, "Not implemented");
+	index_t id = this->get_index(body);
+	ERR_FAIL_COND_MSG(id == 0, "Invalid RID");
+	int count = (*this->fn_table.body_get_collision_exception_count)(id);
+	for (int i = 0; i < count; i++) {
+		index_t exception = (*this->fn_table.body_get_collision_exception)(id, i);
+		if (exception != 0) {
+			list->push_back(this->reverse_rids.get(exception));
+		}
+	}
 }
 
 void PluggablePhysicsServer::init() {
@@ -148,9 +170,8 @@ void PluggablePhysicsServer::step(float delta) {
 				Variant event_type = event.added ? 0 : 1;
 				Variant rid = this->reverse_rids.get(event.id);
 				Variant object_id = event.object_id;
-				// Bullets skip this, so shall we I suppose (less work for me)
-				Variant body_shape = 0;
-				Variant area_shape = 0;
+				Variant body_shape = event.body_shape;
+				Variant area_shape = event.area_shape;
 
 				const Variant *argv[5] = { &event_type, &rid, &object_id, &body_shape, &area_shape };
 
@@ -163,6 +184,7 @@ void PluggablePhysicsServer::step(float delta) {
 	// Remove any invalid callbacks
 	for (int i = 0; i < invalid_callbacks.size(); i++) {
 		this->area_body_monitor_callbacks.erase(invalid_callbacks[i]);
+		(*this->fn_table.area_set_monitor_callback)(invalid_callbacks[i], false);
 	}
 	invalid_callbacks.resize(0);
 
@@ -181,9 +203,8 @@ void PluggablePhysicsServer::step(float delta) {
 				Variant event_type = event.added ? 0 : 1;
 				Variant rid = this->reverse_rids.get(event.id);
 				Variant object_id = event.object_id;
-				// Bullets skips this, so shall we I suppose (less work for me)
-				Variant body_shape = 0;
-				Variant area_shape = 0;
+				Variant body_shape = event.body_shape;
+				Variant area_shape = event.area_shape;
 
 				const Variant *argv[5] = { &event_type, &rid, &object_id, &body_shape, &area_shape };
 
@@ -195,7 +216,8 @@ void PluggablePhysicsServer::step(float delta) {
 
 	// Remove any invalid callbacks
 	for (int i = 0; i < invalid_callbacks.size(); i++) {
-		this->area_body_monitor_callbacks.erase(invalid_callbacks[i]);
+		this->area_area_monitor_callbacks.erase(invalid_callbacks[i]);
+		(*this->fn_table.area_set_area_monitor_callback)(invalid_callbacks[i], false);
 	}
 }
 
@@ -1,4 +1,4 @@
-use crate::body::Body;
+use crate::body::{AreaOverride, Body};
 use crate::indices::{self, Indices};
 use crate::server::{AreaIndex, BodyIndex, Instance, MapIndex, ObjectID, ShapeIndex, SpaceIndex};
 use crate::util::*;
@@ -6,8 +6,9 @@ use core::convert::{TryFrom, TryInto};
 use core::mem;
 use gdnative::core_types::*;
 use rapier3d::dynamics::{RigidBody, RigidBodyBuilder, RigidBodyHandle, RigidBodySet};
-use rapier3d::geometry::{Collider, ColliderHandle, InteractionGroups};
+use rapier3d::geometry::{Collider, ColliderHandle, ColliderSet, InteractionGroups};
 use rapier3d::na::{Isometry3, Point3};
+use rapier3d::prelude::{ActiveEvents, ActiveHooks};
 
 pub struct Area {
 	index: Option<AreaIndex>,
@@ -22,16 +23,38 @@ pub struct Area {
 	gravity_force: f32,
 	gravity_distance_scale: f32,
 	gravity_is_point: bool,
+	gravity_falloff: GravityFalloff,
+	gravity_cutoff: f32,
 
 	object_id: Option<ObjectID>,
 	space_override_mode: SpaceOverrideMode,
+	wind: Option<Wind>,
+	wind_step: u32,
+	fluid: Option<Fluid>,
 	monitorable: bool,
+	body_monitoring: bool,
+	area_monitoring: bool,
 	ray_pickable: bool,
 	interaction_groups: InteractionGroups,
 
-	intersecting_bodies: Vec<BodyIndex>,
-	body_events: Vec<(BodyIndex, bool)>,
-	area_events: Vec<(AreaIndex, bool)>,
+	intersecting_bodies: Vec<(BodyIndex, u32, u32)>,
+	body_entries: Vec<(BodyIndex, u64)>,
+	intersecting_areas: Vec<(AreaIndex, u32, u32)>,
+	rescan_intersections: bool,
+	body_events: Vec<MonitorEvent<BodyIndex>>,
+	area_events: Vec<MonitorEvent<AreaIndex>>,
+	pending_body_events: Vec<MonitorEvent<BodyIndex>>,
+	pending_area_events: Vec<MonitorEvent<AreaIndex>>,
+}
+
+/// An intersection event of an area with a body or another area
+#[derive(Clone, Copy)]
+pub struct MonitorEvent<I> {
+	index: I,
+	object_id: Option<ObjectID>,
+	other_shape: u32,
+	self_shape: u32,
+	intersecting: bool,
 }
 
 pub struct Shape {
@@ -66,6 +89,58 @@ pub struct PointGravity {
 	point: Vector3,
 	gravity: f32,
 	distance_scale: f32,
+	falloff: GravityFalloff,
+	cutoff: f32,
+}
+
+/// Determines how the strength of point gravity decreases with the distance to the gravity point
+#[derive(Clone, Debug)]
+pub enum GravityFalloff {
+	/// The strength decreases with the gravity distance scale, like in Godot
+	DistanceScale,
+	/// The strength is the same everywhere
+	None,
+	/// The strength decreases linearly and is zero at the radius
+	Linear { radius: f32 },
+	/// The strength decreases with the inverse square of the distance, starting at the minimum
+	/// radius
+	InverseSquare { min_radius: f32 },
+	/// The strength is multiplied with a curve, which is sampled at evenly spaced distances from
+	/// the gravity point up to the radius. Beyond the radius the last sample is used.
+	Curve { radius: f32, samples: Vec<f32> },
+}
+
+/// A wind that pushes the bodies inside an area towards its velocity
+#[derive(Clone)]
+pub struct Wind {
+	direction: Vector3,
+	strength: f32,
+	drag: f32,
+	attenuation: WindAttenuation,
+	source: Vector3,
+	range: f32,
+	turbulence: f32,
+	seed: u32,
+}
+
+/// A fluid that makes the bodies inside an area float and slows them down
+#[derive(Clone)]
+pub struct Fluid {
+	density: f32,
+	surface: Plane,
+	linear_drag: f32,
+	angular_drag: f32,
+}
+
+/// Determines how the strength of a wind decreases with the distance to its source
+#[derive(Clone, Copy, Debug)]
+pub enum WindAttenuation {
+	/// The strength is the same everywhere
+	Constant,
+	/// The strength decreases linearly and is zero at the range
+	Linear,
+	/// The strength decreases with the inverse square of the distance, starting at the range
+	InverseSquare,
 }
 
 pub struct RigidbodyUserdata(u128);
@@ -90,16 +165,28 @@ impl Area {
 			gravity_force: 0.0,
 			gravity_direction: Vector3::new(0.0, -1.0, 0.0),
 			gravity_distance_scale: 0.0,
+			gravity_falloff: GravityFalloff::DistanceScale,
+			gravity_cutoff: 0.0,
 
 			object_id: None,
 			space_override_mode: SpaceOverrideMode::Disabled,
+			wind: None,
+			wind_step: 0,
+			fluid: None,
 			monitorable: false,
+			body_monitoring: false,
+			area_monitoring: false,
 			ray_pickable: false,
 			interaction_groups: InteractionGroups::default(),
 
 			intersecting_bodies: Vec::new(),
+			body_entries: Vec::new(),
+			intersecting_areas: Vec::new(),
+			rescan_intersections: false,
 			body_events: Vec::new(),
 			area_events: Vec::new(),
+			pending_body_events: Vec::new(),
+			pending_area_events: Vec::new(),
 		}
 	}
 
@@ -158,6 +245,11 @@ impl Area {
 		self.index.unwrap()
 	}
 
+	/// Returns the amount of shapes attached to this area
+	pub fn shape_count(&self) -> u32 {
+		self.shapes.len() as u32
+	}
+
 	/// Gets a shape at a given index
 	pub fn get_shape(&self, index: u32) -> Option<&Shape> {
 		self.shapes.get(index as usize)
@@ -201,7 +293,7 @@ impl Area {
 	/// Returns the [`Transform`] of this shape
 	pub fn get_shape_transform(&self, shape: u32) -> Option<Transform> {
 		self.get_shape(shape)
-			.map(|shape| isometry_to_transform(&shape.transform))
+			.map(|shape| isometry_and_scale_to_transform(&shape.transform, shape.scale))
 	}
 
 	/// Sets the [`Transform`] of this shape
@@ -211,26 +303,38 @@ impl Area {
 	/// [`true`] if the shape existed, [`false`] otherwise
 	pub fn set_shape_transform(&mut self, shape_index: u32, transform: &Transform) -> bool {
 		if let Some(shape) = self.get_shape_mut(shape_index) {
-			let transform = transform_to_isometry(*transform);
+			let (transform, scale) = transform_to_isometry_and_scale(transform);
 			shape.transform = transform;
-			if let Some(((_, colliders), space)) = self.instance.as_attached() {
-				if let Some(collider) = colliders[shape_index as usize] {
-					space
-						.map_mut(|space| {
-							space
-								.get_collider_mut(collider)
-								.expect("Invalid collider handle")
-								.set_position_wrt_parent(transform);
-						})
-						.expect("Invalid space index");
-				}
-			}
+			shape.scale = scale;
+			self.update_collider(shape_index);
 			true
 		} else {
 			false
 		}
 	}
 
+	/// Rescales and moves the collider of the given shape (if any) according to the shape
+	fn update_collider(&self, shape_index: u32) {
+		let shape = &self.shapes[shape_index as usize];
+		if let Some(((_, colliders), space)) = self.instance.as_attached() {
+			if let Some(collider) = colliders[shape_index as usize] {
+				let scaled = shape
+					.shape
+					.map(|s| s.scaled(shape.collider_scale(self.scale)))
+					.expect("Invalid shape index");
+				space
+					.map_mut(|space| {
+						let c = space
+							.get_collider_mut(collider)
+							.expect("Invalid collider handle");
+						c.set_shape(scaled);
+						c.set_position_wrt_parent(shape.transform);
+					})
+					.expect("Invalid space index");
+			}
+		}
+	}
+
 	/// Sets whether this shape is enabled or not
 	///
 	/// # Returns
@@ -239,7 +343,8 @@ impl Area {
 	pub fn set_shape_enabled(&mut self, shape_index: u32, enabled: bool) -> bool {
 		if let Some(shape) = self.get_shape_mut(shape_index) {
 			shape.enabled = enabled;
-			let (shape, transform, scale) = (shape.shape, shape.transform, shape.scale);
+			let scale = shape.collider_scale(self.scale);
+			let (shape, transform) = (shape.shape, shape.transform);
 			let (index, monitorable, ray_pickable) =
 				(self.index(), self.monitorable, self.ray_pickable);
 			if let Some(((body, colliders), space)) = self.instance.as_attached_mut() {
@@ -250,15 +355,13 @@ impl Area {
 								shape
 									.map(|s| {
 										let mut c = s.build(transform, scale, true);
-										// `set_collider_userdata` doesn't work because of limitations in
-										// the borrow checker.
-										c.user_data = ColliderUserdata::new(
+										Self::init_collider(
+											&mut c,
 											index,
 											monitorable,
 											ray_pickable,
-											u32::MAX,
-										)
-										.into();
+											shape_index,
+										);
 										let c = space.add_collider(c, *body);
 										colliders[shape_index as usize] = Some(c);
 									})
@@ -294,9 +397,13 @@ impl Area {
 	///
 	/// [`true`] if the shape existed, [`false`] otherwise
 	pub fn set_shape_index(&mut self, shape: u32, index: ShapeIndex) -> bool {
-		self.get_shape_mut(shape)
-			.map(|shape| shape.shape = index)
-			.is_some()
+		if let Some(shp) = self.get_shape_mut(shape) {
+			shp.shape = index;
+			self.update_collider(shape);
+			true
+		} else {
+			false
+		}
 	}
 
 	/// Returns the space this area is attached to, if any
@@ -321,13 +428,20 @@ impl Area {
 				.map_mut(|space| {
 					let body = space.add_body(body);
 					let mut colliders = Vec::with_capacity(self.shapes.len());
-					for shape in self.shapes.iter() {
+					for (i, shape) in self.shapes.iter().enumerate() {
 						if shape.enabled {
 							shape
 								.shape
 								.map(|s| {
-									let mut c = s.build(shape.transform, shape.scale, true);
-									self.set_collider_userdata(&mut c);
+									let scale = shape.collider_scale(self.scale);
+									let mut c = s.build(shape.transform, scale, true);
+									Self::init_collider(
+										&mut c,
+										self.index(),
+										self.monitorable,
+										self.ray_pickable,
+										i as u32,
+									);
 									let c = space.add_collider(c, body);
 									colliders.push(Some(c));
 								})
@@ -356,6 +470,8 @@ impl Area {
 				self.gravity_force,
 				vec_na_to_gd(point.coords),
 				self.gravity_distance_scale,
+				self.gravity_falloff.clone(),
+				self.gravity_cutoff,
 			))
 		} else {
 			Gravity::Direction(DirectionGravity::new(
@@ -364,26 +480,75 @@ impl Area {
 		}
 	}
 
+	/// Returns the gravity direction of this area, or the gravity point if it is point gravity
+	pub fn gravity_direction(&self) -> Vector3 {
+		self.gravity_direction
+	}
+
 	/// Sets the gravity direction of this area
 	pub fn set_gravity_direction(&mut self, direction: Vector3) {
 		self.gravity_direction = direction;
 	}
 
+	/// Returns the gravity force of this area
+	pub fn gravity_force(&self) -> f32 {
+		self.gravity_force
+	}
+
 	/// Sets the gravity force of this area
 	pub fn set_gravity_force(&mut self, force: f32) {
 		self.gravity_force = force;
 	}
 
+	/// Returns the gravity distance scale of this area
+	pub fn gravity_distance_scale(&self) -> f32 {
+		self.gravity_distance_scale
+	}
+
 	/// Sets the gravity distance scale of this area. Only applies if the gravity is point gravity
 	pub fn set_gravity_distance_scale(&mut self, scale: f32) {
 		self.gravity_distance_scale = scale;
 	}
 
+	/// Returns whether the gravity is point or direction gravity
+	pub fn is_point_gravity(&self) -> bool {
+		self.gravity_is_point
+	}
+
 	/// Sets whether the gravity is point or direction gravity
 	pub fn set_point_gravity(&mut self, enable: bool) {
 		self.gravity_is_point = enable;
 	}
 
+	/// Returns how the strength of point gravity decreases with the distance
+	pub fn gravity_falloff(&self) -> &GravityFalloff {
+		&self.gravity_falloff
+	}
+
+	/// Sets how the strength of point gravity decreases with the distance. Only applies if the
+	/// gravity is point gravity
+	pub fn set_gravity_falloff(&mut self, falloff: GravityFalloff) {
+		self.gravity_falloff = falloff;
+	}
+
+	/// Returns the distance beyond which the area doesn't override the gravity and damp of bodies,
+	/// or `0` if there is no limit
+	pub fn gravity_cutoff(&self) -> f32 {
+		self.gravity_cutoff
+	}
+
+	/// Sets the distance from the gravity point beyond which the area doesn't override the
+	/// gravity and damp of bodies, as if the body isn't in the area. A distance of `0` or less
+	/// means there is no limit. Only applies if the gravity is point gravity
+	pub fn set_gravity_cutoff(&mut self, cutoff: f32) {
+		self.gravity_cutoff = cutoff;
+	}
+
+	/// Returns the order in which this area will be processed compared to other areas
+	pub fn priority(&self) -> i32 {
+		self.priority
+	}
+
 	/// Sets the order in which this area will be processed compared to other areas
 	pub fn set_priority(&mut self, priority: i32) {
 		self.priority = priority;
@@ -393,11 +558,21 @@ impl Area {
 		}
 	}
 
+	/// Returns the linear damp override of this area
+	pub fn linear_damp(&self) -> f32 {
+		self.linear_damp
+	}
+
 	/// Sets the linear damp override of this area. A negative value disables it.
 	pub fn set_linear_damp(&mut self, damp: f32) {
 		self.linear_damp = damp;
 	}
 
+	/// Returns the angular damp override of this area
+	pub fn angular_damp(&self) -> f32 {
+		self.angular_damp
+	}
+
 	/// Sets the angular damp override of this area. A negative value disables it.
 	pub fn set_angular_damp(&mut self, damp: f32) {
 		self.angular_damp = damp;
@@ -423,6 +598,31 @@ impl Area {
 		self.space_override_mode = mode;
 	}
 
+	/// Returns the wind of this area, if any
+	pub fn wind(&self) -> Option<&Wind> {
+		self.wind.as_ref()
+	}
+
+	/// Sets the wind of this area. Wind is applied regardless of the space override mode.
+	pub fn set_wind(&mut self, wind: Option<Wind>) {
+		self.wind = wind;
+	}
+
+	/// Returns the fluid of this area, if any
+	pub fn fluid(&self) -> Option<&Fluid> {
+		self.fluid.as_ref()
+	}
+
+	/// Sets the fluid of this area. Fluids are applied regardless of the space override mode.
+	pub fn set_fluid(&mut self, fluid: Option<Fluid>) {
+		self.fluid = fluid;
+	}
+
+	/// Returns the wind of this area mutably, if any
+	pub fn wind_mut(&mut self) -> Option<&mut Wind> {
+		self.wind.as_mut()
+	}
+
 	/// Returns whether this area can be monitored by other areas
 	pub fn monitorable(&self) -> bool {
 		self.monitorable
@@ -431,6 +631,7 @@ impl Area {
 	/// Sets whether this area can be monitored by other areas
 	pub fn set_monitorable(&mut self, enable: bool) {
 		self.monitorable = enable;
+		self.rescan_intersections = true;
 		self.map_rigidbody_mut(|body| {
 			let mut ud = RigidbodyUserdata::try_from(body.user_data).expect("Invalid user data");
 			ud.set_monitorable(enable);
@@ -438,27 +639,82 @@ impl Area {
 		});
 	}
 
-	/// Returns the transform of this area
+	/// Returns whether events are generated for bodies entering or exiting this area
+	pub fn is_monitoring_bodies(&self) -> bool {
+		self.body_monitoring
+	}
+
+	/// Sets whether events are generated for bodies entering or exiting this area. Intersecting
+	/// bodies are tracked regardless, as those are also needed for space overrides and overlap
+	/// queries.
+	pub fn set_body_monitoring(&mut self, enable: bool) {
+		self.body_monitoring = enable;
+		if !enable {
+			self.body_events.clear();
+			self.pending_body_events.clear();
+		}
+	}
+
+	/// Returns whether events are generated for monitorable areas entering or exiting this area
+	pub fn is_monitoring_areas(&self) -> bool {
+		self.area_monitoring
+	}
+
+	/// Sets whether events are generated for monitorable areas entering or exiting this area.
+	/// Intersecting areas are tracked regardless, as those are also needed for overlap queries.
+	pub fn set_area_monitoring(&mut self, enable: bool) {
+		self.area_monitoring = enable;
+		if !enable {
+			self.area_events.clear();
+			self.pending_area_events.clear();
+		}
+	}
+
+	/// Returns whether the intersections of this area need to be rescanned and clears the flag
+	pub fn take_rescan_intersections(&mut self) -> bool {
+		mem::replace(&mut self.rescan_intersections, false)
+	}
+
+	/// Returns the transform of this area, including scale
 	pub fn transform(&self) -> Transform {
-		// TODO scaling
+		let scale = self.scale;
 		match &self.instance {
 			Instance::Attached((body, _), space) => space
 				.map(|space| {
 					let body = space.bodies().get(*body).expect("Invalid body handle");
-					isometry_to_transform(body.position())
+					isometry_and_scale_to_transform(body.position(), scale)
 				})
 				.expect("Invalid space"),
-			Instance::Loose(body) => isometry_to_transform(body.position()),
+			Instance::Loose(body) => isometry_and_scale_to_transform(body.position(), scale),
 		}
 	}
 
-	/// Sets the transform of this area
+	/// Sets the transform of this area and rescales the colliders if the scale changed
 	pub fn set_transform(&mut self, transform: &Transform) {
 		let (position, scale) = transform_to_isometry_and_scale(transform);
-		self.scale = scale;
-		// TODO apply scaling
+		let shapes = if scale != self.scale {
+			self.scale = scale;
+			let shapes = self
+				.shapes
+				.iter()
+				.map(|shape| {
+					if shape.enabled {
+						let shape = shape
+							.shape
+							.map(|s| s.scaled(shape.collider_scale(scale)))
+							.expect("Invalid shape index");
+						Some(shape)
+					} else {
+						None
+					}
+				})
+				.collect::<Vec<_>>();
+			Some(shapes)
+		} else {
+			None
+		};
 		match &mut self.instance {
-			Instance::Attached((body, _), space) => {
+			Instance::Attached((body, colliders), space) => {
 				space
 					.map_mut(|space| {
 						space
@@ -466,6 +722,14 @@ impl Area {
 							.get_mut(*body)
 							.expect("Invalid body handle")
 							.set_position(position, false);
+						for (handle, shape) in colliders.iter().zip(shapes.into_iter().flatten()) {
+							if let (Some(handle), Some(shape)) = (handle, shape) {
+								space
+									.get_collider_mut(*handle)
+									.expect("Invalid collider handle")
+									.set_shape(shape);
+							}
+						}
 					})
 					.expect("Invalid space");
 			}
@@ -491,21 +755,49 @@ impl Area {
 		});
 	}
 
+	/// Returns the collision layer of this area
+	pub fn layer(&self) -> u32 {
+		self.interaction_groups.memberships
+	}
+
+	/// Returns whether this area detects bodies and areas in the given collision layer
+	pub fn detects_layer(&self, layer: u32) -> bool {
+		self.interaction_groups.filter & layer != 0
+	}
+
 	/// Sets the collision layer of this area
 	pub fn set_layer(&mut self, layer: u32) {
 		self.interaction_groups = self.interaction_groups.with_memberships(layer);
+		self.rescan_intersections = true;
 	}
 
 	/// Sets the collision mask of this area
 	pub fn set_mask(&mut self, mask: u32) {
 		self.interaction_groups = self.interaction_groups.with_filter(mask);
+		self.rescan_intersections = true;
 	}
 
-	/// Stores the area's index in the given [`Collider`]
-	fn set_collider_userdata(&self, collider: &mut Collider) {
-		collider.user_data =
-			ColliderUserdata::new(self.index(), self.monitorable, self.ray_pickable, u32::MAX)
-				.into();
+	/// Stores the area's index in the given [`Collider`] and enables intersection events and
+	/// filtering, the latter of which is needed to detect non-dynamic bodies and other areas.
+	///
+	/// The interaction groups of the area are not written to the collider. Rapier only reports
+	/// an intersection if each collider is in the filter of the other, while an area detects
+	/// anything in its mask regardless of the mask of the other body or area. The collider's
+	/// groups pass everything instead, so [`filter_intersection_pair`] is the only filter and
+	/// the layer and mask are checked when the events are processed.
+	///
+	/// [`filter_intersection_pair`]: rapier3d::pipeline::PhysicsHooks::filter_intersection_pair
+	fn init_collider(
+		collider: &mut Collider,
+		index: AreaIndex,
+		monitorable: bool,
+		ray_pickable: bool,
+		shape: u32,
+	) {
+		collider.user_data = ColliderUserdata::new(index, monitorable, ray_pickable, shape).into();
+		collider.set_collision_groups(InteractionGroups::all());
+		collider.set_active_hooks(ActiveHooks::FILTER_INTERSECTION_PAIR);
+		collider.set_active_events(ActiveEvents::INTERSECTION_EVENTS);
 	}
 
 	/// Stores the area's index in the given [`RigidBody`]
@@ -531,49 +823,135 @@ impl Area {
 		RigidbodyUserdata::try_from(body.user_data).ok()
 	}
 
-	/// Clears all intersection events. This is necessary in case nothing is popping events
+	/// Clears all intersection events. This is necessary in case nothing is popping events.
+	///
+	/// Events of bodies and areas that were freed since the last step are kept, as those can't
+	/// be detected during the step anymore.
 	pub fn clear_events(&mut self) {
 		self.body_events.clear();
 		self.area_events.clear();
+		self.body_events.append(&mut self.pending_body_events);
+		self.area_events.append(&mut self.pending_area_events);
 	}
 
-	/// Adds an intersection event with a body
-	pub fn push_body_event(&mut self, body: BodyIndex, intersecting: bool) {
-		self.body_events.push((body, intersecting));
-		if intersecting {
-			self.intersecting_bodies.push(body);
-		} else {
-			let i = self
-				.intersecting_bodies
-				.iter()
-				.position(|v| *v == body)
-				.expect("Body index not found");
+	/// Returns the bodies that currently intersect this area, along with the index of the shape
+	/// of the body and the index of the shape of this area
+	pub fn intersecting_bodies(&self) -> &[(BodyIndex, u32, u32)] {
+		&self.intersecting_bodies[..]
+	}
+
+	/// Returns the areas that currently intersect this area, along with the index of the shape
+	/// of the other area and the index of the shape of this area
+	pub fn intersecting_areas(&self) -> &[(AreaIndex, u32, u32)] {
+		&self.intersecting_areas[..]
+	}
+
+	/// Adds an intersection event with a body. The event is only stored if bodies are monitored.
+	///
+	/// `entry` is used to order the space overrides of areas with equal priorities. It must be
+	/// higher than the `entry` of any earlier event in the same space.
+	pub fn push_body_event(&mut self, event: MonitorEvent<BodyIndex>, entry: u64) {
+		let body = event.index();
+		let pair = (body, event.other_shape(), event.self_shape());
+		let is_body = |v: &(BodyIndex, u32, u32)| v.0 == body;
+		if event.intersecting() {
+			if self.intersecting_bodies.contains(&pair) {
+				// Already found by a rescan
+				return;
+			}
+			// The body only enters if none of its other shapes are already inside
+			if !self.intersecting_bodies.iter().any(is_body) {
+				self.body_entries.push((body, entry));
+			}
+			self.intersecting_bodies.push(pair);
+		} else if let Some(i) = self.intersecting_bodies.iter().position(|v| *v == pair) {
 			self.intersecting_bodies.remove(i);
+			if !self.intersecting_bodies.iter().any(is_body) {
+				self.body_entries.retain(|&(b, _)| b != body);
+			}
+		} else {
+			// The body wasn't detected when it entered
+			return;
+		}
+		if self.body_monitoring {
+			self.body_events.push(event);
 		}
 	}
 
 	/// Retrieves and removes an intersection event with a body
-	pub fn pop_body_event(&mut self) -> Option<(BodyIndex, bool)> {
+	pub fn pop_body_event(&mut self) -> Option<MonitorEvent<BodyIndex>> {
 		self.body_events.pop()
 	}
 
-	/// Adds an intersection event with an area
-	pub fn push_area_event(&mut self, area: AreaIndex, intersecting: bool) {
-		self.area_events.push((area, intersecting));
+	/// Adds an intersection event with an area. The event is only stored if areas are monitored.
+	pub fn push_area_event(&mut self, event: MonitorEvent<AreaIndex>) {
+		let pair = (event.index(), event.other_shape(), event.self_shape());
+		if event.intersecting() {
+			if self.intersecting_areas.contains(&pair) {
+				// Already found by a rescan
+				return;
+			}
+			self.intersecting_areas.push(pair);
+		} else if let Some(i) = self.intersecting_areas.iter().position(|v| *v == pair) {
+			self.intersecting_areas.remove(i);
+		} else {
+			// The area wasn't detected when it entered
+			return;
+		}
+		if self.area_monitoring {
+			self.area_events.push(event);
+		}
 	}
 
 	/// Retrieves and removes an intersection event with an area
-	pub fn pop_area_event(&mut self) -> Option<(AreaIndex, bool)> {
+	pub fn pop_area_event(&mut self) -> Option<MonitorEvent<AreaIndex>> {
 		self.area_events.pop()
 	}
 
+	/// Removes a freed body from the intersecting bodies. An exit event is sent during the next
+	/// step for each shape of the body that was intersecting this area.
+	pub fn remove_freed_body(&mut self, body: BodyIndex, object_id: Option<ObjectID>) {
+		let (pending, monitoring) = (&mut self.pending_body_events, self.body_monitoring);
+		self.intersecting_bodies.retain(|&(b, other, this)| {
+			if b == body && monitoring {
+				pending.push(MonitorEvent::new(body, object_id, other, this, false));
+			}
+			b != body
+		});
+		self.body_entries.retain(|&(b, _)| b != body);
+	}
+
+	/// Removes a freed area from the intersecting areas. An exit event is sent during the next
+	/// step for each shape of the area that was intersecting this area.
+	pub fn remove_freed_area(&mut self, area: AreaIndex, object_id: Option<ObjectID>) {
+		let (pending, monitoring) = (&mut self.pending_area_events, self.area_monitoring);
+		self.intersecting_areas.retain(|&(a, other, this)| {
+			if a == area && monitoring {
+				pending.push(MonitorEvent::new(area, object_id, other, this, false));
+			}
+			a != area
+		});
+	}
+
+	/// Frees this area, removing it and its colliders from it's attached space (if any)
+	pub fn free(self) {
+		if let Instance::Attached((body, _), space) = &self.instance {
+			space
+				.map_mut(|space| space.remove_area(*body))
+				.expect("Invalid space");
+		}
+	}
+
 	/// Applies the space override (if any) to the bodies
 	pub fn apply_events(
 		&mut self,
 		area: &RigidBody,
 		bodies: &mut Indices<Body>,
 		rigid_bodies: &RigidBodySet,
+		colliders: &ColliderSet,
 	) {
+		self.apply_wind(area, bodies, rigid_bodies);
+		self.apply_fluid(area, bodies, rigid_bodies, colliders);
 		let (replace, lock) = match self.space_override_mode {
 			SpaceOverrideMode::Disabled => return,
 			SpaceOverrideMode::Combine => (false, false),
@@ -582,18 +960,316 @@ impl Area {
 			SpaceOverrideMode::ReplaceCombine => (true, false),
 		};
 		let gravity = self.gravity(area);
-		for body in self.intersecting_bodies.iter() {
-			let body = bodies.get_mut(body.into()).expect("Invalid body index");
-			body.area_apply_overrides(
-				rigid_bodies,
+		for &(index, entry) in self.body_entries.iter() {
+			let area_override = AreaOverride {
+				priority: self.priority,
+				entry,
 				replace,
-				&gravity,
-				self.linear_damp,
-				self.angular_damp,
 				lock,
-			);
+				linear_damp: self.linear_damp,
+				angular_damp: self.angular_damp,
+			};
+			let body = bodies.get_mut(index.into()).expect("Invalid body index");
+			body.area_add_override(rigid_bodies, &gravity, area_override);
+		}
+	}
+
+	/// Applies the wind force (if any) to the dynamic bodies
+	fn apply_wind(
+		&mut self,
+		area: &RigidBody,
+		bodies: &mut Indices<Body>,
+		rigid_bodies: &RigidBodySet,
+	) {
+		self.wind_step = self.wind_step.wrapping_add(1);
+		let wind = if let Some(wind) = &self.wind {
+			wind
+		} else {
+			return;
+		};
+		let source = vec_gd_to_na(wind.source.component_mul(self.scale));
+		let source = area.position() * Point3::from(source);
+		let source = vec_na_to_gd(source.coords);
+		let mut applied = Vec::new();
+		for &(index, _, _) in self.intersecting_bodies.iter() {
+			// A body may intersect with multiple shapes
+			if applied.contains(&index) {
+				continue;
+			}
+			applied.push(index);
+			let body = bodies.get_mut(index.into()).expect("Invalid body index");
+			if let Some((rb, _)) = body.as_attached() {
+				let rb = &rigid_bodies[rb];
+				if rb.is_dynamic() {
+					let com = rb.position() * rb.mass_properties().local_com;
+					let noise = if wind.turbulence > 0.0 {
+						turbulence_noise(wind.seed, self.wind_step, index)
+					} else {
+						Vector3::zero()
+					};
+					let velocity = wind.velocity(source, vec_na_to_gd(com.coords), noise);
+					let force = (velocity - vec_na_to_gd(*rb.linvel())) * wind.drag;
+					body.area_add_force(force);
+				}
+			}
+		}
+	}
+
+	/// Applies buoyancy and drag of the fluid (if any) to the dynamic bodies
+	fn apply_fluid(
+		&self,
+		area: &RigidBody,
+		bodies: &mut Indices<Body>,
+		rigid_bodies: &RigidBodySet,
+		colliders: &ColliderSet,
+	) {
+		let fluid = if let Some(fluid) = &self.fluid {
+			fluid
+		} else {
+			return;
+		};
+		// Move the surface to global space. Normals are scaled by the inverse of the scale, so the
+		// plane stays the same set of (scaled) points under non-uniform scale.
+		let normal = vec_gd_to_na(fluid.surface.normal).component_div(&vec_gd_to_na(self.scale));
+		let length = normal.norm();
+		let point = normal * (fluid.surface.d / (length * length));
+		let normal = area.position().rotation * (normal / length);
+		let point = area.position() * Point3::from(point);
+		let mut applied = Vec::new();
+		for &(index, _, _) in self.intersecting_bodies.iter() {
+			// A body may intersect with multiple shapes
+			if applied.contains(&index) {
+				continue;
+			}
+			applied.push(index);
+			let body = bodies.get_mut(index.into()).expect("Invalid body index");
+			let rb = if let Some((rb, _)) = body.as_attached() {
+				&rigid_bodies[rb]
+			} else {
+				continue;
+			};
+			if !rb.is_dynamic() {
+				continue;
+			}
+			let mut total_volume = 0.0;
+			let mut submerged_volume = 0.0;
+			for &collider in rb.colliders() {
+				let collider = &colliders[collider];
+				if collider.is_sensor() {
+					continue;
+				}
+				// Estimate which part of the shape is below the surface with its AABB. The
+				// submerged part of the AABB is measured along the normal of the surface.
+				let aabb = collider.compute_aabb();
+				let center = aabb.center();
+				let half = aabb.half_extents();
+				let h = normal.x.abs() * half.x + normal.y.abs() * half.y + normal.z.abs() * half.z;
+				let top = h.min(normal.dot(&(point - center)));
+				let volume = collider.shape().mass_properties(1.0).mass();
+				total_volume += volume;
+				if top <= -h || h <= 0.0 {
+					continue;
+				}
+				let fraction = (top + h) / (2.0 * h);
+				let volume = volume * fraction;
+				submerged_volume += volume;
+				let center_of_buoyancy = center + normal * ((top - h) * 0.5);
+				body.area_add_buoyancy(
+					fluid.density * volume,
+					vec_na_to_gd(center_of_buoyancy.coords),
+				);
+			}
+			if submerged_volume > 0.0 {
+				// Drag is proportional to how much of the body is submerged
+				let fraction = submerged_volume / total_volume;
+				let force = -rb.linvel() * (fluid.linear_drag * fraction);
+				let torque = -rb.angvel() * (fluid.angular_drag * fraction);
+				body.area_add_force(vec_na_to_gd(force));
+				body.area_add_torque(vec_na_to_gd(torque));
+			}
+		}
+	}
+}
+
+impl Fluid {
+	pub fn new(density: f32, surface: Plane, linear_drag: f32, angular_drag: f32) -> Self {
+		Self {
+			density,
+			surface,
+			linear_drag,
+			angular_drag,
 		}
 	}
+
+	/// Returns the density of the fluid
+	pub fn density(&self) -> f32 {
+		self.density
+	}
+
+	/// Returns the surface of the fluid in local space. The normal points out of the fluid.
+	pub fn surface(&self) -> Plane {
+		self.surface
+	}
+
+	/// Returns the linear drag of the fluid, i.e. the force per unit of velocity of a fully
+	/// submerged body
+	pub fn linear_drag(&self) -> f32 {
+		self.linear_drag
+	}
+
+	/// Returns the angular drag of the fluid, i.e. the torque per unit of angular velocity of a
+	/// fully submerged body
+	pub fn angular_drag(&self) -> f32 {
+		self.angular_drag
+	}
+}
+
+impl Wind {
+	pub fn new(direction: Vector3, strength: f32, drag: f32) -> Self {
+		Self {
+			direction,
+			strength,
+			drag,
+			attenuation: WindAttenuation::Constant,
+			source: Vector3::zero(),
+			range: 0.0,
+			turbulence: 0.0,
+			seed: 0,
+		}
+	}
+
+	/// Returns the direction of the wind in global space
+	pub fn direction(&self) -> Vector3 {
+		self.direction
+	}
+
+	/// Returns the speed of the wind
+	pub fn strength(&self) -> f32 {
+		self.strength
+	}
+
+	/// Returns how strongly bodies are pulled along, i.e. the force per unit of velocity
+	/// difference between the body and the wind
+	pub fn drag(&self) -> f32 {
+		self.drag
+	}
+
+	/// Returns the attenuation mode, the source point in local space and the range
+	pub fn attenuation(&self) -> (WindAttenuation, Vector3, f32) {
+		(self.attenuation, self.source, self.range)
+	}
+
+	/// Sets the attenuation mode, the source point in local space and the range. For linear
+	/// attenuation the range is the distance at which the wind stops, for inverse-square
+	/// attenuation it is the distance from which the wind weakens. A range of `0` or less means
+	/// no limit for linear attenuation and `1` for inverse-square attenuation.
+	pub fn set_attenuation(&mut self, attenuation: WindAttenuation, source: Vector3, range: f32) {
+		self.attenuation = attenuation;
+		self.source = source;
+		self.range = range;
+	}
+
+	/// Returns the turbulence and the seed used to generate it
+	pub fn turbulence(&self) -> (f32, u32) {
+		(self.turbulence, self.seed)
+	}
+
+	/// Sets the turbulence, which is the maximum random deviation of the wind relative to its
+	/// strength. A turbulence of `0` disables it.
+	pub fn set_turbulence(&mut self, turbulence: f32, seed: u32) {
+		self.turbulence = turbulence;
+		self.seed = seed;
+	}
+
+	/// Returns the velocity of the wind at the given position. The source must be in global
+	/// space and the components of the noise must be between `-1` and `1`.
+	fn velocity(&self, source: Vector3, position: Vector3, noise: Vector3) -> Vector3 {
+		let distance = (position - source).length();
+		let factor = match self.attenuation {
+			WindAttenuation::Constant => 1.0,
+			WindAttenuation::Linear if self.range > 0.0 => (1.0 - distance / self.range).max(0.0),
+			WindAttenuation::Linear => 1.0,
+			WindAttenuation::InverseSquare => {
+				let min = if self.range > 0.0 { self.range } else { 1.0 };
+				let f = min / distance.max(min);
+				f * f
+			}
+		};
+		let direction = if self.direction == Vector3::zero() {
+			self.direction
+		} else {
+			self.direction.normalize()
+		};
+		(direction + noise * self.turbulence) * (self.strength * factor)
+	}
+}
+
+/// Returns a pseudo-random vector with components between `-1` and `1` that is unique for each
+/// seed, step and body.
+fn turbulence_noise(seed: u32, step: u32, body: BodyIndex) -> Vector3 {
+	// SplitMix64, which is good enough for this and doesn't need any extra dependencies
+	let mut x = (u64::from(seed) << 32 | u64::from(step))
+		^ (u64::from(body.index()) << 16 | u64::from(body.generation()));
+	let mut next = || {
+		x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut z = x;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^= z >> 31;
+		(z >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+	};
+	Vector3::new(next(), next(), next())
+}
+
+impl Shape {
+	/// Returns the scale of the collider of this shape, which includes the scale of the area
+	fn collider_scale(&self, area_scale: Vector3) -> Vector3 {
+		let scale = self.transform.rotation * vec_gd_to_na(self.scale);
+		vec_na_to_gd(vec_gd_to_na(area_scale).component_mul(&scale))
+	}
+}
+
+impl<I: Copy> MonitorEvent<I> {
+	pub fn new(
+		index: I,
+		object_id: Option<ObjectID>,
+		other_shape: u32,
+		self_shape: u32,
+		intersecting: bool,
+	) -> Self {
+		Self {
+			index,
+			object_id,
+			other_shape,
+			self_shape,
+			intersecting,
+		}
+	}
+
+	/// Returns the index of the body or area
+	pub fn index(&self) -> I {
+		self.index
+	}
+
+	/// Returns the object ID of the body or area
+	pub fn object_id(&self) -> Option<ObjectID> {
+		self.object_id
+	}
+
+	/// Returns the index of the shape of the body or area
+	pub fn other_shape(&self) -> u32 {
+		self.other_shape
+	}
+
+	/// Returns the index of the shape of this area
+	pub fn self_shape(&self) -> u32 {
+		self.self_shape
+	}
+
+	/// Returns whether the body or area entered or exited
+	pub fn intersecting(&self) -> bool {
+		self.intersecting
+	}
 }
 
 impl DirectionGravity {
@@ -607,11 +1283,19 @@ impl DirectionGravity {
 }
 
 impl PointGravity {
-	fn new(gravity: f32, point: Vector3, distance_scale: f32) -> Self {
+	fn new(
+		gravity: f32,
+		point: Vector3,
+		distance_scale: f32,
+		falloff: GravityFalloff,
+		cutoff: f32,
+	) -> Self {
 		Self {
 			point,
 			gravity,
 			distance_scale,
+			falloff,
+			cutoff,
 		}
 	}
 
@@ -626,6 +1310,55 @@ impl PointGravity {
 	pub fn distance_scale(&self) -> f32 {
 		self.distance_scale
 	}
+
+	/// Returns whether the given position in global space is beyond the cutoff distance
+	pub fn is_beyond_cutoff(&self, position: Vector3) -> bool {
+		self.cutoff > 0.0 && (self.point - position).length() > self.cutoff
+	}
+
+	/// Returns the gravity at the given position in global space
+	pub fn gravity_at(&self, position: Vector3) -> Vector3 {
+		let dir = self.point - position;
+		let dist = dir.length();
+		if dist <= 0.0 {
+			// We can't divide by 0, so pretend there is no force in the middle of a thing
+			return Vector3::zero();
+		}
+		if self.is_beyond_cutoff(position) {
+			return Vector3::zero();
+		}
+		let factor = match &self.falloff {
+			GravityFalloff::DistanceScale if self.distance_scale > 0.0 => {
+				// Based on code in rigid_body_bullet.cpp
+				let f = dist.mul_add(self.distance_scale, 1.0);
+				1.0 / (f * f)
+			}
+			GravityFalloff::DistanceScale | GravityFalloff::None => 1.0,
+			GravityFalloff::Linear { radius } if *radius > 0.0 => (1.0 - dist / radius).max(0.0),
+			GravityFalloff::Linear { .. } => 1.0,
+			GravityFalloff::InverseSquare { min_radius } => {
+				let f = min_radius / dist.max(*min_radius);
+				f * f
+			}
+			GravityFalloff::Curve { radius, samples } => sample_curve(samples, dist / radius),
+		};
+		dir * (self.gravity * factor / dist)
+	}
+}
+
+/// Samples a curve made of evenly spaced points between `0` and `1`, interpolating linearly
+/// between them. Values of `t` outside that range are clamped.
+fn sample_curve(samples: &[f32], t: f32) -> f32 {
+	match samples.len() {
+		0 => 1.0,
+		1 => samples[0],
+		len => {
+			let t = t.max(0.0).min(1.0) * (len - 1) as f32;
+			let i = (t as usize).min(len - 2);
+			let f = t - i as f32;
+			samples[i] + (samples[i + 1] - samples[i]) * f
+		}
+	}
 }
 
 impl RigidbodyUserdata {
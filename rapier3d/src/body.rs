@@ -4,6 +4,7 @@ use crate::indices::Indices;
 use crate::server::{BodyIndex, Instance, MapIndex, ObjectID, Shape, ShapeIndex, SpaceIndex};
 use crate::space::Space;
 use crate::util::*;
+use core::cmp::Ordering;
 use core::convert::{TryFrom, TryInto};
 use gdnative::core_types::*;
 use rapier3d::dynamics::{MassProperties, RigidBody, RigidBodyHandle, RigidBodySet, RigidBodyType};
@@ -19,6 +20,9 @@ pub struct BodyShape {
 	transform: Isometry<f32>,
 	scale: Vector3,
 	enabled: bool,
+	friction: Option<f32>,
+	restitution: Option<f32>,
+	density: f32,
 }
 
 #[derive(Debug)]
@@ -28,6 +32,11 @@ pub struct ContactEvent {
 	other_shape: u32,
 	self_shape: u32,
 	normal: Vector3,
+	impulse: f32,
+	depth: f32,
+	other_velocity: Vector3,
+	relative_velocity: Vector3,
+	other_user_flags: u32,
 }
 
 pub struct RigidBodyUserdata(u128);
@@ -41,42 +50,86 @@ pub struct Body {
 	object_id: Option<ObjectID>,
 	shapes: Vec<BodyShape>,
 	scale: Vector3,
+	previous_isometry: Isometry<f32>,
 
 	exclusions: Vec<BodyIndex>,
 	collision_groups: InteractionGroups,
-	#[allow(dead_code)]
 	ray_pickable: bool,
+	user_flags: u32,
 
 	linear_damp: f32,
 	angular_damp: f32,
+	gravity_scale: f32,
+	custom_gravity: Option<(Vector3, GravityRule)>,
+	omit_force_integration: bool,
+	kinematic_velocity_based: bool,
+	dominance_group: i8,
 	restitution: f32,
 	friction: f32,
 
 	mass_properties: MassProperties,
+	custom_inertia: Option<(na::Vector3<f32>, na::UnitQuaternion<f32>)>,
+	mass_from_density: bool,
+
+	translation_locks: [bool; 3],
 
 	inertia_stale: bool,
 
-	area_gravity: Option<Vector3>,
-	area_linear_damp: Option<(f32, u32)>,
-	area_angular_damp: Option<(f32, u32)>,
-	area_replace: bool,
-	area_lock: bool,
+	area_overrides: Vec<(AreaOverride, Vector3)>,
+	area_force: Vector3,
+	area_torque: Vector3,
+	area_displaced_mass: f32,
+	area_buoyancy_moment: Vector3,
 
 	max_contacts: u32,
+	contacts_depth_threshold: f32,
 	contacts: Vec<ContactEvent>,
 
 	index: Option<BodyIndex>,
 }
 
+/// Determines how the custom gravity of a body combines with the gravity of areas.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GravityRule {
+	/// The custom gravity takes the place of the space's gravity, i.e. areas that stop the space's
+	/// gravity from applying also stop it and other areas add to it.
+	Replace,
+	/// The gravity of any areas is always added to the custom gravity.
+	Add,
+	/// The gravity of areas is ignored.
+	IgnoreAreas,
+}
+
+/// The space override of an area that affects a body.
+#[derive(Clone, Copy, Debug)]
+pub struct AreaOverride {
+	/// The priority of the area.
+	pub priority: i32,
+	/// When the body entered the area. Higher values mean the body entered it later.
+	pub entry: u64,
+	/// Whether the overrides of areas with a higher priority are discarded.
+	pub replace: bool,
+	/// Whether areas with a lower priority and the space are ignored.
+	pub lock: bool,
+	/// The linear damp of the area. A negative damp leaves the damp unchanged.
+	pub linear_damp: f32,
+	/// The angular damp of the area. A negative damp leaves the damp unchanged.
+	pub angular_damp: f32,
+}
+
 #[derive(Debug)]
 pub struct AlreadyExcluded;
 
+#[derive(Debug)]
+pub struct NotExcluded;
+
 #[derive(Debug)]
 pub struct InvalidShape;
 
 impl Body {
 	pub fn new(body: RigidBody) -> Self {
 		Self {
+			previous_isometry: *body.position(),
 			body: Instance::loose(body),
 			object_id: None,
 			shapes: Vec::new(),
@@ -85,23 +138,34 @@ impl Body {
 			exclusions: Vec::new(),
 			collision_groups: InteractionGroups::new(1, 1),
 			ray_pickable: true,
+			user_flags: 0,
 
 			angular_damp: -1.0,
 			linear_damp: -1.0,
+			gravity_scale: 1.0,
+			custom_gravity: None,
+			omit_force_integration: false,
+			kinematic_velocity_based: false,
+			dominance_group: 0,
 			restitution: 0.0,
 			friction: 1.0,
 
 			mass_properties: MassProperties::new(Point3::origin(), 1.0, na::Vector3::zeros()),
+			custom_inertia: None,
+			mass_from_density: false,
+
+			translation_locks: [false; 3],
 
 			inertia_stale: false,
 
-			area_gravity: None,
-			area_linear_damp: None,
-			area_angular_damp: None,
-			area_lock: false,
-			area_replace: false,
+			area_overrides: Vec::new(),
+			area_force: Vector3::zero(),
+			area_torque: Vector3::zero(),
+			area_displaced_mass: 0.0,
+			area_buoyancy_moment: Vector3::zero(),
 
 			max_contacts: 0,
+			contacts_depth_threshold: 0.0,
 			contacts: Vec::new(),
 
 			index: None,
@@ -169,26 +233,40 @@ impl Body {
 			transform: iso,
 			enabled,
 			scale: scl,
+			friction: None,
+			restitution: None,
+			density: 1.0,
 		};
-		let self_scale = self.scale;
-		if let Instance::Attached((rb, colliders), space) = &mut self.body {
-			if enabled {
-				space
-					.map_mut(|space| {
-						let shape_scale = iso.rotation * vec_gd_to_na(scl);
-						let shape_scale = vec_gd_to_na(self_scale).component_mul(&shape_scale);
-						let shape_scale = vec_na_to_gd(shape_scale);
-						let mut collider = shape.build(body_shape.transform, shape_scale, false);
-						collider.set_active_hooks(
-							ActiveHooks::FILTER_CONTACT_PAIRS | ActiveHooks::MODIFY_SOLVER_CONTACTS,
-						);
-						collider.set_active_events(ActiveEvents::INTERSECTION_EVENTS);
-						let collider = space.add_collider(collider, *rb);
-						colliders.push(Some(collider));
-					})
+		if let Some((rb, space)) = self.as_attached() {
+			let collider = if enabled {
+				let shape_scale = iso.rotation * vec_gd_to_na(scl);
+				let shape_scale = vec_gd_to_na(self.scale).component_mul(&shape_scale);
+				let shape_scale = vec_na_to_gd(shape_scale);
+				let mut collider = shape.build(body_shape.transform, shape_scale, false);
+				collider.set_collision_groups(self.collider_collision_groups());
+				collider.set_solver_groups(self.collision_groups);
+				collider.set_restitution(self.shape_restitution(&body_shape));
+				collider.set_friction(self.shape_friction(&body_shape));
+				collider.set_active_hooks(
+					ActiveHooks::FILTER_CONTACT_PAIRS | ActiveHooks::MODIFY_SOLVER_CONTACTS,
+				);
+				collider.set_active_events(ActiveEvents::INTERSECTION_EVENTS);
+				collider.user_data = ColliderUserdata::new(
+					self.index(),
+					self.max_contacts > 0,
+					self.ray_pickable,
+					self.shapes.len() as u32,
+				)
+				.into();
+				let collider = space
+					.map_mut(|space| space.add_collider(collider, rb))
 					.expect("Invalid space");
+				Some(collider)
 			} else {
-				colliders.push(None);
+				None
+			};
+			if let Instance::Attached((_, colliders), _) = &mut self.body {
+				colliders.push(collider);
 			}
 		}
 		self.shapes.push(body_shape);
@@ -199,22 +277,51 @@ impl Body {
 	fn recalculate_inertia(&mut self, body: &mut RigidBody, shapes: &mut Indices<Shape>) {
 		// TODO avoid calculating the MassProperties twice
 		let mut mp = Vec::with_capacity(self.shapes.len());
+		let mut densities = Vec::with_capacity(self.shapes.len());
 		for shape in self.shapes.iter() {
 			if shape.enabled {
 				let trf = shape.transform;
+				densities.push(shape.density);
 				let shape = shapes.get(shape.index.into()).expect("Invalid shape index");
 				mp.push((trf, shape.shape().clone()));
 			}
 		}
-		let mp_f = MassProperties::from_compound(1.0, &mp[..]);
-		let ratio = mp_f.inv_mass / body.mass_properties().inv_mass;
-		let mut mp = MassProperties::from_compound(ratio, &mp[..]);
+		let density_mp = if self.mass_from_density {
+			Some(
+				mp.iter()
+					.zip(densities.into_iter())
+					.map(|((trf, shape), density)| shape.mass_properties(density).transform_by(trf))
+					.sum::<MassProperties>(),
+			)
+		} else {
+			None
+		};
+		// Without any enabled shapes the derived mass is zero, which would make the body
+		// immovable, so use the configured mass instead.
+		let mut mp = if let Some(mp) = density_mp.filter(|mp| mp.mass() > 0.0) {
+			mp
+		} else {
+			let inv_mass = self.mass_properties.inv_mass;
+			let mp_f = MassProperties::from_compound(1.0, &mp[..]);
+			let ratio = mp_f.inv_mass / inv_mass;
+			let mut mp = MassProperties::from_compound(ratio, &mp[..]);
+			mp.inv_mass = inv_mass;
+			// The center of mass is set with `set_local_com` instead
+			mp.local_com = body.mass_properties().local_com;
+			mp
+		};
 		// Workaround for https://github.com/dimforge/parry/issues/20
 		if mp.local_com.x.is_nan() || mp.local_com.x.is_infinite() {
 			mp.local_com = Point3::new(0.0, 0.0, 0.0);
 		};
-		mp.inv_mass = body.mass_properties().inv_mass;
-		mp.local_com = body.mass_properties().local_com;
+		if let Some((inertia, frame)) = self.custom_inertia {
+			mp = MassProperties::with_principal_inertia_frame(
+				mp.local_com,
+				mp.mass(),
+				inertia,
+				frame,
+			);
+		}
 		body.set_mass_properties(mp, true);
 		self.inertia_stale = false;
 	}
@@ -223,6 +330,23 @@ impl Body {
 		self.object_id
 	}
 
+	/// Returns the amount of shapes attached to this body
+	pub fn shape_count(&self) -> u32 {
+		self.shapes.len() as u32
+	}
+
+	/// Returns the [`ShapeIndex`] of the given shape
+	pub fn get_shape_index(&self, shape: u32) -> Option<ShapeIndex> {
+		self.shapes.get(shape as usize).map(|shape| shape.index)
+	}
+
+	/// Returns the [`Transform`] of the given shape, including scale
+	pub fn get_shape_transform(&self, shape: u32) -> Option<Transform> {
+		self.shapes
+			.get(shape as usize)
+			.map(|shape| isometry_and_scale_to_transform(&shape.transform, shape.scale))
+	}
+
 	/// Creates shapes according to shape enable status and transform
 	fn create_shapes(&self) -> Vec<Option<(SharedShape, &Isometry<f32>)>> {
 		let mut shapes = Vec::with_capacity(self.shapes.len());
@@ -250,9 +374,10 @@ impl Body {
 			if let Some((shape, transform)) = e {
 				let mut collider = ColliderBuilder::new(shape)
 					.position(*transform)
-					.collision_groups(self.collision_groups)
-					.restitution(self.restitution)
-					.friction(self.friction)
+					.collision_groups(self.collider_collision_groups())
+					.solver_groups(self.collision_groups)
+					.restitution(self.shape_restitution(&self.shapes[i]))
+					.friction(self.shape_friction(&self.shapes[i]))
 					.active_hooks(
 						ActiveHooks::FILTER_CONTACT_PAIRS | ActiveHooks::MODIFY_SOLVER_CONTACTS,
 					)
@@ -288,134 +413,183 @@ impl Body {
 	/// Frees this body, removing it from it's attached space (if any)
 	pub fn free(self) {
 		if let Instance::Attached((rb, _), space) = &self.body {
+			let self_index = self.index();
 			space
 				.map_mut(|space| {
 					space.remove_body(*rb);
+					for &exclude in self.exclusions.iter() {
+						let _ = space.remove_body_exclusion(self_index, exclude);
+					}
 				})
 				.expect("Invalid space");
 		}
 	}
 
-	/// Adds any forces and damp overrides from an area
-	pub fn area_apply_overrides(
+	/// Adds the gravity and damp overrides of an area. The overrides are applied with
+	/// [`Self::apply_area_overrides`], so the areas can be added in any order.
+	///
+	/// If the body is beyond the cutoff of point gravity the area is ignored entirely, so it
+	/// can't replace or lock the overrides of other areas either.
+	pub fn area_add_override(
 		&mut self,
 		bodies: &RigidBodySet,
-		replace: bool,
 		gravity: &Gravity,
-		linear_damp: f32,
-		angular_damp: f32,
-		lock: bool,
+		area_override: AreaOverride,
 	) {
-		if !self.area_lock {
-			let g = match gravity {
-				Gravity::Direction(g) => g.gravity(),
-				Gravity::Point(p) => {
-					if let Instance::Attached((rb, _), _) = &self.body {
-						// Based on code in rigid_body_bullet.cpp
-						// I'm not sure what the logic behind the scale factor is but w/e
-						let body = &bodies[*rb];
-						let dir = p.point() - vec_na_to_gd(body.position().translation.vector);
-						let dist = dir.length();
-						if dist > 0.0 {
-							let scale = p.distance_scale();
-							if scale > 0.0 {
-								let f = dist.mul_add(p.distance_scale(), 1.0);
-								(dir * p.gravity()) / ((f * f) * dist)
-							} else {
-								(dir * p.gravity()) / dist
-							}
-						} else {
-							// We can't divide by 0, so pretend there is no force in the middle of a thing
-							Vector3::zero()
-						}
-					} else {
-						unreachable!();
+		let g = match gravity {
+			Gravity::Direction(g) => g.gravity(),
+			Gravity::Point(p) => {
+				if let Instance::Attached((rb, _), _) = &self.body {
+					let position = vec_na_to_gd(bodies[*rb].position().translation.vector);
+					if p.is_beyond_cutoff(position) {
+						return;
 					}
-				}
-			};
-			if replace {
-				self.area_gravity = Some(g);
-				self.area_linear_damp = if linear_damp >= 0.0 {
-					Some((linear_damp, 1))
+					p.gravity_at(position)
 				} else {
-					None
-				};
-				self.area_angular_damp = if angular_damp >= 0.0 {
-					Some((angular_damp, 1))
-				} else {
-					None
-				};
-				self.area_replace = true;
-			} else {
-				self.area_gravity = if let Some(ag) = self.area_gravity {
-					Some(ag + g)
-				} else {
-					Some(g)
-				};
-				self.area_linear_damp = if let Some((d, i)) = self.area_linear_damp {
-					Some((linear_damp + d, i + 1))
-				} else {
-					Some((linear_damp, 1))
-				};
-				self.area_angular_damp = if let Some((d, i)) = self.area_linear_damp {
-					Some((angular_damp + d, i + 1))
-				} else {
-					Some((angular_damp, 1))
-				};
+					unreachable!();
+				}
 			}
-			self.area_lock = lock;
-		}
+		};
+		self.area_overrides.push((area_override, g));
+	}
+
+	/// Adds a force from an area, such as wind. It is applied with the other area overrides.
+	pub fn area_add_force(&mut self, force: Vector3) {
+		self.area_force += force;
+	}
+
+	/// Adds a torque from an area, such as buoyancy. It is applied with the other area overrides.
+	pub fn area_add_torque(&mut self, torque: Vector3) {
+		self.area_torque += torque;
 	}
 
-	/// Applies any forces and damp overrides added by areas and clears the area lock
+	/// Adds the mass of fluid displaced by a part of this body and the global center of buoyancy
+	/// of that part. The buoyancy depends on the gravity acting on this body, so it's applied
+	/// with the other area overrides.
+	pub fn area_add_buoyancy(&mut self, displaced_mass: f32, center: Vector3) {
+		self.area_displaced_mass += displaced_mass;
+		self.area_buoyancy_moment += center * displaced_mass;
+	}
+
+	/// Applies any forces and damp overrides added by areas and clears them.
+	///
+	/// Areas with a higher priority go first. If the priorities are equal, the area the body
+	/// entered last goes first, like Godot's Bullet backend does. An area that replaces discards
+	/// the overrides of the areas before it and an area that locks prevents any areas after it
+	/// and the space's defaults from applying.
 	pub fn apply_area_overrides(
 		&mut self,
 		body: &mut RigidBody,
+		space_gravity: Vector3,
 		space_linear_damp: f32,
 		space_angular_damp: f32,
 	) {
-		let current_gravity_scale = body.gravity_scale();
-		#[allow(clippy::float_cmp)] // Shut up Clippy
-		if let Some(g) = self.area_gravity {
-			let s = if self.area_replace { 0.0 } else { 1.0 };
-			body.set_gravity_scale(s, s != current_gravity_scale);
-			body.apply_force(vec_gd_to_na(g) * body.mass(), s != current_gravity_scale);
-		} else {
-			body.set_gravity_scale(1.0, current_gravity_scale != 1.0);
+		self.area_overrides
+			.sort_unstable_by(|(a, _), (b, _)| (b.priority, b.entry).cmp(&(a.priority, a.entry)));
+		let mut area_gravity = None;
+		let mut area_linear_damp = 0.0;
+		let mut area_angular_damp = 0.0;
+		let mut area_lock = false;
+		for &(o, g) in self.area_overrides.iter() {
+			let combine = |acc: f32, damp: f32| match (o.replace, damp >= 0.0) {
+				(_, false) => acc,
+				(true, true) => damp,
+				(false, true) => acc + damp,
+			};
+			area_gravity = match area_gravity {
+				Some(ag) if !o.replace => Some(ag + g),
+				_ => Some(g),
+			};
+			area_linear_damp = combine(area_linear_damp, o.linear_damp);
+			area_angular_damp = combine(area_angular_damp, o.angular_damp);
+			area_lock = o.lock;
+			if area_lock {
+				break;
+			}
 		}
-		body.set_linear_damping(if let Some((d, i)) = self.area_linear_damp {
-			let i = i as f32;
-			if self.area_replace {
-				d / i
-			} else if self.linear_damp < 0.0 {
-				(space_linear_damp + d * i) / (i + 1.0)
-			} else {
-				(self.linear_damp + d * i) / (i + 1.0)
+		self.area_overrides.clear();
+
+		// Rapier applies the gravity of the space with the gravity scale, any other gravity is
+		// applied as a force.
+		let scale = if self.omit_force_integration {
+			0.0
+		} else {
+			self.gravity_scale
+		};
+		let (space_scale, gravity) = match (self.custom_gravity, area_gravity) {
+			(None, None) => (scale, None),
+			(None, Some(a)) if area_lock => (0.0, Some(a)),
+			(None, Some(a)) => (scale, Some(a)),
+			(Some((g, rule)), a) => {
+				let g = match (rule, a) {
+					(_, None) | (GravityRule::IgnoreAreas, _) => g,
+					(GravityRule::Replace, Some(a)) if area_lock => a,
+					(GravityRule::Replace, Some(a)) | (GravityRule::Add, Some(a)) => g + a,
+				};
+				(0.0, Some(g))
 			}
-		} else if self.linear_damp < 0.0 {
-			space_linear_damp
+		};
+		// The gravity that actually acts on the body, which also determines the buoyancy
+		let total_gravity = if space_scale > 0.0 {
+			space_gravity
 		} else {
-			self.linear_damp
-		});
-		body.set_angular_damping(if let Some((d, i)) = self.area_angular_damp {
-			let i = i as f32;
-			if self.area_replace {
-				d / i
-			} else if self.angular_damp < 0.0 {
-				(space_angular_damp + d * i) / (i + 1.0)
+			Vector3::zero()
+		};
+		let total_gravity = (total_gravity + gravity.unwrap_or_else(Vector3::zero)) * scale;
+		// Rapier can't leave out the locked axes when applying the gravity of the space itself
+		let partially_locked = self.is_translation_partially_locked();
+		let (space_scale, gravity) = if partially_locked && space_scale > 0.0 {
+			let gravity = gravity.unwrap_or_else(Vector3::zero);
+			(0.0, Some(gravity + space_gravity))
+		} else {
+			(space_scale, gravity)
+		};
+		#[allow(clippy::float_cmp)] // Shut up Clippy
+		let wake = space_scale != body.gravity_scale();
+		body.set_gravity_scale(space_scale, wake);
+		if let Some(g) = gravity {
+			let mut force = vec_gd_to_na(g * scale) * body.mass();
+			self.project_translation_locks(&mut force);
+			body.apply_force(force, wake);
+		}
+		if !self.omit_force_integration && self.area_force != Vector3::zero() {
+			let mut force = vec_gd_to_na(self.area_force);
+			self.project_translation_locks(&mut force);
+			body.apply_force(force, true);
+		}
+		if !self.omit_force_integration && self.area_torque != Vector3::zero() {
+			body.apply_torque(vec_gd_to_na(self.area_torque), true);
+		}
+		if !self.omit_force_integration && self.area_displaced_mass > 0.0 {
+			let buoyancy = -total_gravity * self.area_displaced_mass;
+			let com = body.position() * body.mass_properties().local_com;
+			let arm =
+				self.area_buoyancy_moment / self.area_displaced_mass - vec_na_to_gd(com.coords);
+			let mut force = vec_gd_to_na(buoyancy);
+			self.project_translation_locks(&mut force);
+			body.apply_force(force, true);
+			body.apply_torque(vec_gd_to_na(arm.cross(buoyancy)), true);
+		}
+		self.area_force = Vector3::zero();
+		self.area_torque = Vector3::zero();
+		self.area_displaced_mass = 0.0;
+		self.area_buoyancy_moment = Vector3::zero();
+		// The damp of the body itself overrides any damp of areas and the space
+		let damp = |own: f32, area: f32, space: f32| {
+			if own >= 0.0 {
+				own
+			} else if area_lock {
+				area
 			} else {
-				(self.angular_damp + d * i) / (i + 1.0)
+				area + space
 			}
-		} else if self.linear_damp < 0.0 {
-			space_angular_damp
-		} else {
-			self.angular_damp
-		});
-		self.area_gravity = None;
-		self.area_linear_damp = None;
-		self.area_angular_damp = None;
-		self.area_replace = false;
-		self.area_lock = false;
+		};
+		body.set_linear_damping(damp(self.linear_damp, area_linear_damp, space_linear_damp));
+		body.set_angular_damping(damp(
+			self.angular_damp,
+			area_angular_damp,
+			space_angular_damp,
+		));
 	}
 
 	/// Adds body with which this body will not collide with
@@ -448,9 +622,61 @@ impl Body {
 		}
 	}
 
+	/// Removes a body with which this body would not collide with
+	///
+	/// # Returns
+	///
+	/// `Err(NotExcluded)` if the bodies didn't exclude each other, `Ok(())` otherwise
+	pub fn remove_exclusion(&mut self, other: &mut Self) -> Result<(), NotExcluded> {
+		let other_index = other.index();
+		if let Some(i) = self.exclusions.iter().position(|&e| e == other_index) {
+			let self_index = self.index();
+			let j = other
+				.exclusions
+				.iter()
+				.position(|&e| e == self_index)
+				.expect("Other body doesn't exclude self");
+			self.exclusions.swap_remove(i);
+			other.exclusions.swap_remove(j);
+			// The exclusion may have been registered by either body, so check both spaces
+			for space in [self.space(), other.space()].iter().flatten() {
+				space
+					.map_mut(|space| {
+						let _ = space.remove_body_exclusion(self_index, other_index);
+					})
+					.expect("Invalid space");
+			}
+			Ok(())
+		} else {
+			Err(NotExcluded)
+		}
+	}
+
+	/// Removes the given body from the list of excluded bodies without updating the other body.
+	/// This is only meant to be used when the other body is being freed.
+	pub fn forget_exclusion(&mut self, other: BodyIndex) {
+		if let Some(i) = self.exclusions.iter().position(|&e| e == other) {
+			self.exclusions.swap_remove(i);
+			if let Some(space) = self.space() {
+				let self_index = self.index();
+				space
+					.map_mut(|space| {
+						let _ = space.remove_body_exclusion(self_index, other);
+					})
+					.expect("Invalid space");
+			}
+		}
+	}
+
+	/// Returns the bodies with which this body will not collide
+	pub fn exclusions(&self) -> &[BodyIndex] {
+		&self.exclusions[..]
+	}
+
 	/// Applies a force to the center of the body . The force must be in global space.
 	pub fn add_central_force(&mut self, force: Vector3) {
-		let force = vec_gd_to_na(force);
+		let mut force = vec_gd_to_na(force);
+		self.project_translation_locks(&mut force);
 		self.map_rigidbody_mut(|body| body.apply_force(force, true));
 	}
 
@@ -458,23 +684,27 @@ impl Body {
 	pub fn add_force_at_position(&mut self, force: Vector3, position: Vector3) {
 		let force = vec_gd_to_na(force);
 		let position = Point3::from(vec_gd_to_na(position));
-		self.map_rigidbody_mut(|body| body.apply_force_at_point(force, position, true));
+		let mut linear_force = force;
+		self.project_translation_locks(&mut linear_force);
+		self.map_rigidbody_mut(|body| {
+			// apply_force_at_point seems to be broken:
+			// https://github.com/dimforge/rapier/issues/216
+			// The torque is also applied separately so it isn't affected by the translation locks
+			let world_com = body.position() * body.mass_properties().local_com;
+			body.apply_force(linear_force, false);
+			body.apply_torque((position - world_com).cross(&force), true);
+		});
 	}
 
 	/// Applies a force to the body at the given position. The force must be in local space.
 	pub fn add_local_force_at_position(&mut self, force: Vector3, position: Vector3) {
 		let force = vec_gd_to_na(force);
 		let position = Point3::from(vec_gd_to_na(position));
-		self.map_rigidbody_mut(|body| {
-			let body_pos = body.position();
-			let force = body_pos.transform_vector(&force);
-			let position = body_pos.transform_point(&position);
-			// apply_force_at_point seems to be broken:
-			// https://github.com/dimforge/rapier/issues/216
-			let world_com = body_pos.transform_point(&body.mass_properties().local_com);
-			body.apply_force(force, false);
-			body.apply_torque((position - world_com).cross(&force), true);
-		});
+		let body_pos = self.map_rigidbody(|body| *body.position());
+		self.add_force_at_position(
+			vec_na_to_gd(body_pos.transform_vector(&force)),
+			vec_na_to_gd(body_pos.transform_point(&position).coords),
+		);
 	}
 
 	/// Applies an impulse to the body at the given position. The impulse must be in global space.
@@ -513,44 +743,106 @@ impl Body {
 		self.map_rigidbody(|body| vec_na_to_gd(body.position().translation.vector))
 	}
 
-	/// Applies the transform and scales the colliders
-	pub fn set_transform(&mut self, transform: &Transform) {
+	/// Returns the transform of this body, including scale
+	pub fn transform(&self) -> Transform {
+		let scale = self.scale;
+		self.map_rigidbody(|body| isometry_and_scale_to_transform(Self::position_of(body), scale))
+	}
+
+	/// Returns the position of a [`RigidBody`]. Position-based kinematic bodies are only moved
+	/// during the step, so this returns the position they will be moved to instead.
+	pub fn position_of(body: &RigidBody) -> &Isometry<f32> {
+		if body.body_type() == RigidBodyType::KinematicPositionBased {
+			body.next_position()
+		} else {
+			body.position()
+		}
+	}
+
+	/// Returns the transform of this body before the last step, including scale
+	pub fn previous_transform(&self) -> Transform {
+		isometry_and_scale_to_transform(&self.previous_isometry, self.scale)
+	}
+
+	/// Returns the transform of this body interpolated between the transform before and after the
+	/// last step, including scale. An `alpha` of `0` returns the transform before the step and an
+	/// `alpha` of `1` the current transform.
+	pub fn interpolated_transform(&self, alpha: f32) -> Transform {
+		let previous = self.previous_isometry;
+		let iso = self.map_rigidbody(|body| previous.lerp_slerp(body.position(), alpha));
+		isometry_and_scale_to_transform(&iso, self.scale)
+	}
+
+	/// Returns the linear velocity of this body
+	pub fn linear_velocity(&self) -> Vector3 {
+		self.map_rigidbody(|body| vec_na_to_gd(*body.linvel()))
+	}
+
+	/// Returns the angular velocity of this body
+	pub fn angular_velocity(&self) -> Vector3 {
+		self.map_rigidbody(|body| vec_na_to_gd(*body.angvel()))
+	}
+
+	/// Returns whether this body is sleeping
+	pub fn is_sleeping(&self) -> bool {
+		self.map_rigidbody(|body| body.is_sleeping())
+	}
+
+	/// Returns whether this body is allowed to sleep
+	pub fn can_sleep(&self) -> bool {
+		self.map_rigidbody(|body| body.activation().threshold >= 0.0)
+	}
+
+	/// Applies the transform and scales the colliders.
+	///
+	/// Position-based kinematic bodies are moved during the next step so the velocity can be
+	/// derived from the movement, unless `teleport` is `true`. Other bodies are always teleported.
+	pub fn set_transform(&mut self, transform: &Transform, teleport: bool) {
 		let (iso, scl) = transform_to_isometry_and_scale(transform);
-		self.scale = scl;
-		self.map_rigidbody_mut(|body| body.set_position(iso, true));
-		// FIXME inefficient as hell
-		let shapes = self
-			.create_shapes()
-			.into_iter()
-			.map(|v| v.map(|v| v.0))
-			.collect::<Vec<_>>();
-		self.inertia_stale |= match &mut self.body {
-			Instance::Attached((body, _), space) => {
+		let teleport = teleport || self.body_type() != RigidBodyType::KinematicPositionBased;
+		if teleport {
+			// Don't interpolate teleports
+			self.previous_isometry = iso;
+		}
+		// Only rescale the shapes if the scale actually changed
+		let shapes = if scl != self.scale {
+			self.scale = scl;
+			let shapes = self
+				.create_shapes()
+				.into_iter()
+				.map(|v| v.map(|v| v.0))
+				.collect::<Vec<_>>();
+			Some(shapes)
+		} else {
+			None
+		};
+		let rescaled = shapes.is_some();
+		match &mut self.body {
+			Instance::Attached((body, colliders), space) => {
 				let body = *body;
 				space
 					.map_mut(|space| {
 						let body = space.get_body_mut(body).expect("Invalid body handle");
-						body.set_position(iso, true);
-						let iter = body
-							.colliders()
-							.iter()
-							.copied()
-							.zip(shapes.into_iter())
-							.collect::<Vec<_>>();
-						for (handle, shape) in iter {
-							space
-								.get_collider_mut(handle)
-								.expect("Invalid collider handle")
-								.set_shape(shape.unwrap());
+						if teleport {
+							body.set_position(iso, true);
+						} else {
+							// Let Rapier move the body during the next step so it can derive the
+							// velocity, which is needed to carry along bodies on top of it.
+							body.set_next_kinematic_position(iso);
+						}
+						for (handle, shape) in colliders.iter().zip(shapes.into_iter().flatten()) {
+							if let (Some(handle), Some(shape)) = (handle, shape) {
+								space
+									.get_collider_mut(*handle)
+									.expect("Invalid collider handle")
+									.set_shape(shape);
+							}
 						}
 					})
 					.expect("Invalid space handle");
-				true
-			}
-			Instance::Loose(body) => {
-				body.set_position(iso, true);
-				false
+				self.inertia_stale |= rescaled;
 			}
+			Instance::Loose(body) => body.set_position(iso, true),
 		}
 	}
 
@@ -580,6 +872,11 @@ impl Body {
 		self.object_id = id;
 	}
 
+	/// Returns whether this body can be picked up by raycasts.
+	pub fn ray_pickable(&self) -> bool {
+		self.ray_pickable
+	}
+
 	/// Sets whether this body can be picked up by raycasts.
 	pub fn set_ray_pickable(&mut self, pickable: bool) {
 		self.ray_pickable = pickable;
@@ -605,6 +902,11 @@ impl Body {
 		}
 	}
 
+	/// Returns the space this body is attached to, if any
+	pub fn space(&self) -> Option<SpaceIndex> {
+		self.as_attached().map(|v| v.1)
+	}
+
 	/// Sets the space of this body
 	pub fn set_space(&mut self, space: &mut Space) {
 		if let Instance::Attached(_, _) = &self.body {
@@ -640,6 +942,7 @@ impl Body {
 				.unwrap()
 				.set_mass_properties(mp, false);
 			let rb = space.get_body_mut(handle).unwrap();
+			rb.set_dominance_group(self.dominance_group);
 			self.recalculate_inertia(rb, &mut ShapeIndex::write_all());
 			Instance::Attached((handle, collider_handles), space.index())
 		} else {
@@ -650,8 +953,15 @@ impl Body {
 	/// Removes the body from it's space, if any
 	pub fn remove_from_space(&mut self) {
 		if let Instance::Attached((body, _), space) = &self.body {
+			let self_index = self.index();
+			let exclusions = &self.exclusions;
 			let body = space
-				.map_mut(|space| space.remove_body(*body).expect("Invalid body handle"))
+				.map_mut(|space| {
+					for &exclude in exclusions.iter() {
+						let _ = space.remove_body_exclusion(self_index, exclude);
+					}
+					space.remove_body(*body).expect("Invalid body handle")
+				})
 				.expect("Failed to modify space");
 			self.body = Instance::loose(body);
 		}
@@ -693,6 +1003,53 @@ impl Body {
 		}
 	}
 
+	/// Replaces the shape at the given position. The transform, enabled state and material of
+	/// the shape are kept.
+	pub fn set_shape(&mut self, shape: u32, index: ShapeIndex) -> Result<(), InvalidShape> {
+		if let Some(shp) = self.shapes.get_mut(shape as usize) {
+			shp.index = index;
+			if let Instance::Attached((_, colliders), space) = &self.body {
+				if let Some(collider) = colliders[shape as usize] {
+					let shape_scale = shp.transform.rotation * vec_gd_to_na(shp.scale);
+					let shape_scale = vec_gd_to_na(self.scale).component_mul(&shape_scale);
+					let shape_scale = vec_na_to_gd(shape_scale);
+					let shape = index
+						.map(|shape| shape.scaled(shape_scale))
+						.expect("Invalid shape index");
+					space
+						.map_mut(|space| {
+							space
+								.get_collider_mut(collider)
+								.expect("Invalid collider handle")
+								.set_shape(shape);
+						})
+						.expect("Invalid space index");
+				}
+			}
+			self.inertia_stale = true;
+			Ok(())
+		} else {
+			Err(InvalidShape)
+		}
+	}
+
+	/// Removes all shapes from this body and any colliders
+	pub fn clear_shapes(&mut self) {
+		self.shapes.clear();
+		if let Instance::Attached((_, colliders), space) = &mut self.body {
+			space
+				.map_mut(|space| {
+					for collider in colliders.drain(..).flatten() {
+						space
+							.remove_collider(collider)
+							.expect("Invalid collider handle");
+					}
+				})
+				.expect("Invalid space handle");
+		}
+		self.inertia_stale = true;
+	}
+
 	/// Sets the transform of a shape and optionally scales it
 	pub fn set_shape_enable(&mut self, shape: u32, enable: bool) -> Result<(), InvalidShape> {
 		if let Some(shape) = self.shapes.get_mut(shape as usize) {
@@ -703,9 +1060,15 @@ impl Body {
 		}
 	}
 
+	/// Returns whether external forces such as gravity are ignored
+	pub fn is_omitting_force_integration(&self) -> bool {
+		self.omit_force_integration
+	}
+
 	/// Sets whether to clear any external forces such as gravity
 	pub fn set_omit_force_integration(&mut self, enable: bool) {
-		let g_scale = if enable { 0.0 } else { 1.0 };
+		self.omit_force_integration = enable;
+		let g_scale = if enable { 0.0 } else { self.gravity_scale };
 		match &mut self.body {
 			Instance::Attached((rb, _), space) => {
 				space
@@ -724,6 +1087,63 @@ impl Body {
 		}
 	}
 
+	/// Returns whether this body is static, kinematic or dynamic
+	pub fn body_type(&self) -> RigidBodyType {
+		self.map_rigidbody(|body| body.body_type())
+	}
+
+	/// Returns whether this body is velocity based when it is kinematic
+	pub fn is_kinematic_velocity_based(&self) -> bool {
+		self.kinematic_velocity_based
+	}
+
+	/// Sets whether this body is moved by setting it's velocity instead of it's transform when it
+	/// is kinematic. Position based kinematic bodies derive their velocity from the transform.
+	pub fn set_kinematic_velocity_based(&mut self, enable: bool) {
+		self.kinematic_velocity_based = enable;
+		if self.body_type().is_kinematic() {
+			self.set_body_type(self.kinematic_type());
+		}
+	}
+
+	/// Returns the type to use when this body is kinematic
+	pub fn kinematic_type(&self) -> RigidBodyType {
+		if self.kinematic_velocity_based {
+			RigidBodyType::KinematicVelocityBased
+		} else {
+			RigidBodyType::KinematicPositionBased
+		}
+	}
+
+	/// Returns the user flags of this body
+	pub fn user_flags(&self) -> u32 {
+		self.user_flags
+	}
+
+	/// Sets the user flags of this body, which can be used by gameplay code to tag bodies. These
+	/// are included in contact and ray results.
+	pub fn set_user_flags(&mut self, flags: u32) {
+		// The flags are also stored in the userdata so they can be read during a step
+		self.user_flags = flags;
+		self.map_rigidbody_mut(|body| {
+			let mut ud = RigidBodyUserdata::try_from(&*body).expect("Invalid body userdata");
+			ud.set_user_flags(flags);
+			body.user_data = ud.into();
+		});
+	}
+
+	/// Returns the dominance group of this body
+	pub fn dominance_group(&self) -> i8 {
+		self.dominance_group
+	}
+
+	/// Sets the dominance group of this body. Dynamic bodies in a higher group are not affected
+	/// by dynamic bodies in a lower group.
+	pub fn set_dominance_group(&mut self, group: i8) {
+		self.dominance_group = group;
+		self.map_rigidbody_mut(|body| body.set_dominance_group(group));
+	}
+
 	/// Sets whether this body is static, kinematic or dynamic
 	pub fn set_body_type(&mut self, status: RigidBodyType) {
 		match &mut self.body {
@@ -743,10 +1163,26 @@ impl Body {
 		}
 	}
 
-	/// Sets the groups of this body
+	/// Returns the groups of this body
+	pub fn groups(&self) -> u32 {
+		self.collision_groups.memberships
+	}
+
+	/// Sets the groups of this body. Areas around the body check whether they still detect it
+	/// during the next step.
 	pub fn set_groups(&mut self, groups: u32) {
 		self.collision_groups = self.collision_groups.with_memberships(groups);
 		self.update_interaction_groups();
+		if let Some((rb, space)) = self.as_attached() {
+			space
+				.map_mut(|space| space.rescan_body_intersections(rb))
+				.expect("Invalid space handle");
+		}
+	}
+
+	/// Returns the mask of this body
+	pub fn mask(&self) -> u32 {
+		self.collision_groups.filter
 	}
 
 	/// Sets the mask of this body
@@ -757,8 +1193,26 @@ impl Body {
 
 	/// Updates the [`InteractionGroups`] of the colliders attached to this body
 	fn update_interaction_groups(&mut self) {
-		let cg = self.collision_groups;
-		self.map_colliders(|collider| collider.set_collision_groups(cg));
+		let (cg, sg) = (self.collider_collision_groups(), self.collision_groups);
+		self.map_colliders(|collider| {
+			collider.set_collision_groups(cg);
+			collider.set_solver_groups(sg);
+		});
+	}
+
+	/// Returns the collision groups of the colliders of this body. Rapier only reports pairs if
+	/// each collider is in the filter of the other, but areas should detect a body even if its
+	/// mask is empty. Hence the filter passes everything and the actual layer and mask are
+	/// stored in the solver groups, which are checked when filtering contact pairs. The layer is
+	/// kept in the collision groups as it is used by queries.
+	fn collider_collision_groups(&self) -> InteractionGroups {
+		self.collision_groups.with_filter(u32::MAX)
+	}
+
+	/// Returns the mass of this body as set with [`Self::set_mass`], even if the mass is derived
+	/// from the density of the shapes
+	pub fn mass(&self) -> f32 {
+		self.mass_properties.mass()
 	}
 
 	/// Sets the mass of this body
@@ -772,33 +1226,121 @@ impl Body {
 		self.inertia_stale = true;
 	}
 
+	/// Returns the linear damp of this body. A negative value means the space's default is used.
+	pub fn linear_damp(&self) -> f32 {
+		self.linear_damp
+	}
+
 	/// Sets the linear damp of this body
 	pub fn set_linear_damp(&mut self, damp: f32) {
 		self.linear_damp = damp;
 		self.map_rigidbody_mut(|body| body.set_linear_damping(damp));
 	}
 
+	/// Returns the angular damp of this body. A negative value means the space's default is used.
+	pub fn angular_damp(&self) -> f32 {
+		self.angular_damp
+	}
+
 	/// Sets the angular damp of this body
 	pub fn set_angular_damp(&mut self, damp: f32) {
 		self.angular_damp = damp;
 		self.map_rigidbody_mut(|body| body.set_angular_damping(damp));
 	}
 
+	/// Returns the gravity scale of this body
+	pub fn gravity_scale(&self) -> f32 {
+		self.gravity_scale
+	}
+
 	/// Sets the gravity scale of this body. This wakes up the body.
 	pub fn set_gravity_scale(&mut self, scale: f32) {
-		self.map_rigidbody_mut(|body| body.set_gravity_scale(scale, true));
+		self.gravity_scale = scale;
+		if !self.omit_force_integration && self.custom_gravity.is_none() {
+			self.map_rigidbody_mut(|body| body.set_gravity_scale(scale, true));
+		}
+	}
+
+	/// Returns the custom gravity of this body and how it combines with the gravity of areas, if
+	/// any.
+	pub fn custom_gravity(&self) -> Option<(Vector3, GravityRule)> {
+		self.custom_gravity
+	}
+
+	/// Sets a custom gravity that is used instead of the space's gravity. `None` makes the body use
+	/// the space's gravity again. The gravity scale still applies.
+	pub fn set_custom_gravity(&mut self, gravity: Option<(Vector3, GravityRule)>) {
+		self.custom_gravity = gravity;
+	}
+
+	/// Returns the restitution of this body
+	pub fn restitution(&self) -> f32 {
+		self.restitution
 	}
 
 	/// Sets the restitution of this body and it's colliders, if any.
 	pub fn set_restitution(&mut self, restitution: f32) {
 		self.restitution = restitution;
-		self.map_colliders(|collider| collider.set_restitution(restitution));
+		self.update_collider_materials();
+	}
+
+	/// Returns the friction of this body
+	pub fn friction(&self) -> f32 {
+		self.friction
 	}
 
 	/// Sets the friction of this body and it's colliders, if any.
 	pub fn set_friction(&mut self, friction: f32) {
 		self.friction = friction;
-		self.map_colliders(|collider| collider.set_friction(friction));
+		self.update_collider_materials();
+	}
+
+	/// Returns the friction and restitution of the given shape, if it overrides those of this body
+	pub fn shape_material(&self, shape: u32) -> Result<Option<(f32, f32)>, InvalidShape> {
+		let shape = self.shapes.get(shape as usize).ok_or(InvalidShape)?;
+		Ok(shape.friction.zip(shape.restitution))
+	}
+
+	/// Sets the friction and restitution of the given shape, overriding those of this body.
+	///
+	/// Like with Godot, a negative friction marks the shape as rough and a negative restitution
+	/// marks it as absorbent. `None` makes the shape use the material of this body again.
+	pub fn set_shape_material(
+		&mut self,
+		shape: u32,
+		material: Option<(f32, f32)>,
+	) -> Result<(), InvalidShape> {
+		let shape = self.shapes.get_mut(shape as usize).ok_or(InvalidShape)?;
+		shape.friction = material.map(|m| m.0);
+		shape.restitution = material.map(|m| m.1);
+		self.update_collider_materials();
+		Ok(())
+	}
+
+	/// Returns the friction of the given shape, which is that of this body unless overridden
+	fn shape_friction(&self, shape: &BodyShape) -> f32 {
+		shape.friction.unwrap_or(self.friction)
+	}
+
+	/// Returns the restitution of the given shape, which is that of this body unless overridden
+	fn shape_restitution(&self, shape: &BodyShape) -> f32 {
+		shape.restitution.unwrap_or(self.restitution)
+	}
+
+	/// Updates the friction and restitution of all colliders, if any.
+	fn update_collider_materials(&mut self) {
+		let materials = self
+			.shapes
+			.iter()
+			.map(|shape| (self.shape_friction(shape), self.shape_restitution(shape)))
+			.collect::<Vec<_>>();
+		self.map_colliders(|collider| {
+			let ud =
+				ColliderUserdata::try_from(collider.user_data).expect("Invalid collider userdata");
+			let (friction, restitution) = materials[ud.shape() as usize];
+			collider.set_friction(friction);
+			collider.set_restitution(restitution);
+		});
 	}
 
 	/// Removes the given shape from this body and any colliders
@@ -816,6 +1358,15 @@ impl Body {
 						.expect("Invalid space handle");
 				}
 			}
+			// Shift the shape index of the remaining colliders
+			self.map_colliders(|collider| {
+				let mut ud = ColliderUserdata::try_from(collider.user_data)
+					.expect("Invalid collider userdata");
+				if ud.shape() > shape {
+					ud.set_shape(ud.shape() - 1);
+					collider.user_data = ud.into();
+				}
+			});
 			Ok(())
 		} else {
 			Err(InvalidShape)
@@ -828,6 +1379,41 @@ impl Body {
 		if self.inertia_stale {
 			self.recalculate_inertia(body, shapes);
 		}
+		// Rapier can only lock all translation axes. If only some are locked, the velocity along
+		// those axes is removed here, forces along them are dropped when they are applied and the
+		// space removes them from the contact normals during the step.
+		if body.is_dynamic() && self.is_translation_partially_locked() {
+			let mut linvel = *body.linvel();
+			self.project_translation_locks(&mut linvel);
+			body.set_linvel(linvel, false);
+		}
+		self.previous_isometry = *body.position();
+	}
+
+	/// Zeroes the components of the given vector along the locked translation axes
+	fn project_translation_locks(&self, vector: &mut na::Vector3<f32>) {
+		for i in self.locked_axes() {
+			vector[i] = 0.0;
+		}
+	}
+
+	/// Returns an iterator over the locked translation axes
+	fn locked_axes(&self) -> impl Iterator<Item = usize> + '_ {
+		self.translation_locks
+			.iter()
+			.enumerate()
+			.filter_map(|(i, &lock)| if lock { Some(i) } else { None })
+	}
+
+	/// Returns whether some but not all translation axes are locked
+	fn is_translation_partially_locked(&self) -> bool {
+		let [x, y, z] = self.translation_locks;
+		(x || y || z) && !(x && y && z)
+	}
+
+	/// Returns the maximum amount of contacts this body keeps track of
+	pub fn max_contacts(&self) -> u32 {
+		self.max_contacts
 	}
 
 	/// Sets the maximum amount of contacts this body should keep track of
@@ -841,18 +1427,60 @@ impl Body {
 				ud.set_monitoring(count != 0);
 				body.user_data = ud.into();
 			});
+			self.map_colliders(|collider| {
+				let mut ud = ColliderUserdata::try_from(collider.user_data)
+					.expect("Invalid collider userdata");
+				ud.set_monitoring(count != 0);
+				collider.user_data = ud.into();
+			});
 		}
 		self.max_contacts = count;
 	}
 
-	/// Adds a contact with another body unless the `max_contacts` limit has been reached,
-	/// in which case it's discarded.
+	/// Returns the depth below which contacts are not reported
+	pub fn contacts_depth_threshold(&self) -> f32 {
+		self.contacts_depth_threshold
+	}
+
+	/// Sets the depth below which contacts are not reported. A threshold of `0` or lower reports
+	/// all touching contacts.
+	pub fn set_contacts_depth_threshold(&mut self, threshold: f32) {
+		self.contacts_depth_threshold = threshold;
+	}
+
+	/// Adds a contact with another body unless the contact isn't deep enough, in which case it's
+	/// discarded. Contacts between the same pair of colliders at practically the same point are
+	/// merged.
+	///
+	/// At most `max_contacts` contacts are kept: once full, a new contact replaces the
+	/// lowest-ranked one if it ranks higher and is discarded otherwise.
+	///
+	/// [`Self::finish_contacts`] must be called once all contacts have been added.
 	pub fn add_contact(&mut self, contact: ContactEvent) {
-		if self.contacts.len() < self.max_contacts as usize {
+		if self.contacts_depth_threshold > 0.0 && contact.depth < self.contacts_depth_threshold {
+			return;
+		}
+		if let Some(c) = self
+			.contacts
+			.iter_mut()
+			.find(|c| c.is_duplicate_of(&contact))
+		{
+			c.merge(contact);
+		} else if self.contacts.len() < self.max_contacts as usize {
 			self.contacts.push(contact);
+		} else if let Some(c) = self.contacts.iter_mut().min_by(|a, b| a.rank(b)) {
+			if contact.rank(c) == Ordering::Greater {
+				*c = contact;
+			}
 		}
 	}
 
+	/// Sorts the contacts such that those with the highest impulse come first, or the deepest ones
+	/// if the impulses are equal.
+	pub fn finish_contacts(&mut self) {
+		self.contacts.sort_unstable_by(|a, b| b.rank(a));
+	}
+
 	/// Returns the amount of active contacts
 	pub fn contact_count(&self) -> u32 {
 		self.contacts.len() as u32
@@ -865,18 +1493,40 @@ impl Body {
 
 	/// Returns whether Continuous Collision Detected (CCD) is enabled
 	pub fn is_ccd_enabled(&self) -> bool {
-		self.map_rigidbody(|body| body.is_ccd_active())
+		self.map_rigidbody(|body| body.is_ccd_enabled())
 	}
 
 	/// Enables or disables Continuous Collision Detected (CCD)
+	///
+	/// For kinematic bodies the space sweeps their colliders before each step, so they push any
+	/// dynamic bodies in their way instead of passing through them.
 	pub fn enable_ccd(&mut self, enable: bool) {
 		self.map_rigidbody_mut(|body| body.enable_ccd(enable));
 	}
 
-	/// Locks this body in place at it's current position, which prevents it from being pushed
-	/// by external forces. It may still rotate around it's origin.
-	pub fn set_translation_lock(&mut self, lock: bool) {
-		self.map_rigidbody_mut(|body| body.lock_translations(lock, false));
+	/// Prevents this body from moving along the given global axis due to external forces. If all
+	/// axes are locked the body is locked in place at it's current position. It may still rotate
+	/// around it's origin.
+	pub fn set_translation_lock(&mut self, axis: Axis, lock: bool) {
+		let axis = match axis {
+			Axis::X => 0,
+			Axis::Y => 1,
+			Axis::Z => 2,
+		};
+		self.translation_locks[axis] = lock;
+		let all = self.translation_locks.iter().all(|&l| l);
+		// The space needs the partial locks during the step to project the contact normals
+		let partial = if self.is_translation_partially_locked() {
+			self.translation_locks
+		} else {
+			[false; 3]
+		};
+		self.map_rigidbody_mut(|body| {
+			body.lock_translations(all, false);
+			let mut ud = RigidBodyUserdata::try_from(&*body).expect("Invalid body userdata");
+			ud.set_partial_translation_locks(partial);
+			body.user_data = ud.into();
+		});
 	}
 
 	/// Prevents this body from rotating due to external forces. It can
@@ -893,9 +1543,14 @@ impl Body {
 		});
 	}
 
-	/// Returns whether this body is locked in place
-	pub fn is_translation_locked(&self) -> bool {
-		self.map_rigidbody(|body| body.is_translation_locked())
+	/// Returns whether this body is prevented from moving along the given global axis
+	pub fn is_translation_locked(&self, axis: Axis) -> bool {
+		let axis = match axis {
+			Axis::X => 0,
+			Axis::Y => 1,
+			Axis::Z => 2,
+		};
+		self.translation_locks[axis]
 	}
 
 	/// Returns whether the given local axis of this body is locked
@@ -921,15 +1576,83 @@ impl Body {
 			body.set_mass_properties(mp, wake_up);
 		})
 	}
+
+	/// Returns the principal inertia of this body.
+	pub fn principal_inertia(&self) -> Vector3 {
+		self.map_rigidbody(|body| vec_na_to_gd(body.mass_properties().principal_inertia()))
+	}
+
+	/// Returns the local frame of the principal inertia of this body.
+	pub fn inertia_frame(&self) -> Basis {
+		self.map_rigidbody(|body| {
+			let frame = body.mass_properties().principal_inertia_local_frame;
+			mat3_to_basis(frame.to_rotation_matrix().matrix())
+		})
+	}
+
+	/// Overrides the principal inertia and it's local frame, which are otherwise derived from the
+	/// shapes. `None` derives the inertia from the shapes again.
+	///
+	/// The frame is orthonormalized, so any scale or skew is discarded. It must not be a
+	/// reflection.
+	pub fn set_custom_inertia(&mut self, inertia: Option<(Vector3, Basis)>) {
+		self.custom_inertia = inertia.map(|(inertia, frame)| {
+			let frame = na::Rotation3::from_matrix(&basis_to_mat3(&frame));
+			(
+				vec_gd_to_na(inertia),
+				na::UnitQuaternion::from_rotation_matrix(&frame),
+			)
+		});
+		self.inertia_stale = true;
+	}
+
+	/// Returns whether the mass is derived from the density of the shapes
+	pub fn is_mass_from_density(&self) -> bool {
+		self.mass_from_density
+	}
+
+	/// Sets whether the mass is derived from the density of the shapes instead of the mass set
+	/// with [`Self::set_mass`]. The mass set with [`Self::set_mass`] is still used if the body
+	/// has no enabled shapes.
+	pub fn set_mass_from_density(&mut self, enable: bool) {
+		self.mass_from_density = enable;
+		self.inertia_stale = true;
+	}
+
+	/// Returns the density of the given shape
+	pub fn shape_density(&self, shape: u32) -> Result<f32, InvalidShape> {
+		self.shapes
+			.get(shape as usize)
+			.map(|shape| shape.density)
+			.ok_or(InvalidShape)
+	}
+
+	/// Sets the density of the given shape, which is only used if the mass is derived from the
+	/// density of the shapes.
+	pub fn set_shape_density(&mut self, shape: u32, density: f32) -> Result<(), InvalidShape> {
+		let shape = self.shapes.get_mut(shape as usize).ok_or(InvalidShape)?;
+		shape.density = density;
+		self.inertia_stale |= self.mass_from_density;
+		Ok(())
+	}
 }
 
 impl ContactEvent {
+	/// Contacts between the same pair of colliders that are closer than this distance are merged
+	const MERGE_DISTANCE: f32 = 0.01;
+
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		position: Vector3,
 		other: BodyIndex,
 		other_shape: u32,
 		self_shape: u32,
 		normal: Vector3,
+		impulse: f32,
+		depth: f32,
+		other_velocity: Vector3,
+		self_velocity: Vector3,
+		other_user_flags: u32,
 	) -> Self {
 		Self {
 			position,
@@ -937,6 +1660,11 @@ impl ContactEvent {
 			other_shape,
 			self_shape,
 			normal,
+			impulse,
+			depth,
+			other_velocity,
+			relative_velocity: other_velocity - self_velocity,
+			other_user_flags,
 		}
 	}
 
@@ -944,6 +1672,40 @@ impl ContactEvent {
 		self.other_index
 	}
 
+	/// Returns whether both contacts are between the same pair of colliders and are at
+	/// practically the same point
+	fn is_duplicate_of(&self, other: &Self) -> bool {
+		self.other_index == other.other_index
+			&& self.other_shape == other.other_shape
+			&& self.self_shape == other.self_shape
+			&& (self.position - other.position).length() < Self::MERGE_DISTANCE
+	}
+
+	/// Merges a duplicate contact into this one. The impulses are summed and the position, normal
+	/// and velocity of the deepest contact are kept.
+	fn merge(&mut self, other: Self) {
+		self.impulse += other.impulse;
+		if other.depth > self.depth {
+			self.position = other.position;
+			self.normal = other.normal;
+			self.depth = other.depth;
+			self.other_velocity = other.other_velocity;
+			self.relative_velocity = other.relative_velocity;
+		}
+	}
+
+	/// Compares the impulse and, if those are equal, the depth of both contacts
+	fn rank(&self, other: &Self) -> Ordering {
+		self.impulse
+			.partial_cmp(&other.impulse)
+			.unwrap_or(Ordering::Equal)
+			.then_with(|| {
+				self.depth
+					.partial_cmp(&other.depth)
+					.unwrap_or(Ordering::Equal)
+			})
+	}
+
 	pub fn object_id(&self) -> Option<ObjectID> {
 		self.other_index
 			.map(|body| body.object_id())
@@ -962,6 +1724,31 @@ impl ContactEvent {
 		self.position
 	}
 
+	/// Returns the impulse applied by the solver to resolve this contact
+	pub fn impulse(&self) -> f32 {
+		self.impulse
+	}
+
+	/// Returns the penetration depth. This is negative if the bodies aren't touching yet.
+	pub fn depth(&self) -> f32 {
+		self.depth
+	}
+
+	/// Returns the velocity of the other body at the contact point
+	pub fn other_velocity(&self) -> Vector3 {
+		self.other_velocity
+	}
+
+	/// Returns the velocity of the other body relative to this body at the contact point
+	pub fn relative_velocity(&self) -> Vector3 {
+		self.relative_velocity
+	}
+
+	/// Returns the user flags of the other body
+	pub fn other_user_flags(&self) -> u32 {
+		self.other_user_flags
+	}
+
 	pub fn local_position(&self, body: &RigidBody) -> Vector3 {
 		let p = Point3::from(vec_gd_to_na(self.position));
 		let p = body.position().inverse_transform_point(&p);
@@ -978,21 +1765,25 @@ impl ContactEvent {
 impl RigidBodyUserdata {
 	const TYPE_MASK: u128 = 0x8000_0000_0000_0000;
 	const MONITORING_MASK: u128 = 0x0001_0000_0000_0000;
+	const TRANSLATION_LOCKS_MASK: u128 = 0x000e_0000_0000_0000;
 	#[allow(dead_code)]
 	const INDEX_MASK: u128 = 0x0000_ffff_ffff_ffff;
+	const USER_FLAGS_MASK: u128 = 0xffff_ffff_0000_0000_0000_0000;
 
 	/// Creates a new userdata intended for rigidbodies. This data is marked to ensure it can
 	/// be identified as belonging to a [`Body`].
 	///
-	/// The data fits entirely within the lower 64 bits of a u128, so the upper 64 bits can be
+	/// The data fits entirely within the lower 96 bits of a u128, so the upper 32 bits can be
 	/// used for other purposes
 	///
 	/// The exact format from right to left is:
 	/// - 32 bits for index
 	/// - 16 bits for generation
 	/// - 1 bit for monitoring
-	/// - 14 bits reserved
+	/// - 3 bits for the translation locks, if only some axes are locked
+	/// - 11 bits reserved
 	/// - 1 bit body type indicator (always `0` for [`Body`])
+	/// - 32 bits for user flags
 	fn new(index: BodyIndex, monitoring: bool) -> Self {
 		let mut s = Self(0);
 		s.set_index(index);
@@ -1022,10 +1813,39 @@ impl RigidBodyUserdata {
 		BodyIndex::new(i, g)
 	}
 
+	/// Stores the translation locks of the body. This should be all `false` unless some but not
+	/// all axes are locked.
+	fn set_partial_translation_locks(&mut self, locks: [bool; 3]) {
+		self.0 &= !Self::TRANSLATION_LOCKS_MASK;
+		for (i, &lock) in locks.iter().enumerate() {
+			self.0 |= (lock as u128) << (49 + i);
+		}
+	}
+
+	/// Returns the locked translation axes of the body if only some of them are locked
+	pub fn partial_translation_locks(&self) -> [bool; 3] {
+		let mut locks = [false; 3];
+		for (i, lock) in locks.iter_mut().enumerate() {
+			*lock = self.0 & (1 << (49 + i)) > 0;
+		}
+		locks
+	}
+
+	/// Stores the user flags of the body
+	fn set_user_flags(&mut self, flags: u32) {
+		self.0 &= !Self::USER_FLAGS_MASK;
+		self.0 |= (flags as u128) << 64;
+	}
+
 	/// Returns whether the body is monitoring for contacts
 	pub fn monitoring(&self) -> bool {
 		self.0 & Self::MONITORING_MASK > 0
 	}
+
+	/// Returns the user flags of the body
+	pub fn user_flags(&self) -> u32 {
+		(self.0 >> 64) as u32
+	}
 }
 
 impl TryFrom<u128> for RigidBodyUserdata {
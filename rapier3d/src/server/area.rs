@@ -54,7 +54,9 @@ pub fn init(ffi: &mut ffi::FFI) {
 	ffi!(ffi, area_get_area_event, get_area_event);
 	ffi!(ffi, area_get_body_event, get_body_event);
 	ffi!(ffi, area_get_object_instance_id, get_object_instance_id);
+	ffi!(ffi, area_get_param, get_param);
 	ffi!(ffi, area_get_shape, get_shape);
+	ffi!(ffi, area_get_shape_count, get_shape_count);
 	ffi!(ffi, area_get_shape_transform, get_shape_transform);
 	ffi!(ffi, area_get_space, get_space);
 	ffi!(ffi, area_get_space_override_mode, get_space_override_mode);
@@ -62,7 +64,13 @@ pub fn init(ffi: &mut ffi::FFI) {
 	ffi!(ffi, area_is_ray_pickable, is_ray_pickable);
 	ffi!(ffi, area_remove_shape, remove_shape);
 	ffi!(ffi, area_set_collision_layer, set_collision_layer);
+	ffi!(
+		ffi,
+		area_set_area_monitor_callback,
+		set_area_monitor_callback
+	);
 	ffi!(ffi, area_set_collision_mask, set_collision_mask);
+	ffi!(ffi, area_set_monitor_callback, set_monitor_callback);
 	ffi!(ffi, area_set_monitorable, set_monitorable);
 	ffi!(ffi, area_set_param, set_param);
 	ffi!(ffi, area_set_ray_pickable, set_ray_pickable);
@@ -74,8 +82,14 @@ pub fn init(ffi: &mut ffi::FFI) {
 	ffi!(ffi, area_set_transform, set_transform);
 }
 
-pub fn free(_area: Area) {
-	godot_error!("TODO");
+/// Frees the given area, removing it from it's attached space (if any)
+pub fn free(area: Area) {
+	let index = area.index();
+	let object_id = area.object_id();
+	for (_, other) in AreaIndex::write_all().iter_mut() {
+		other.remove_freed_area(index, object_id);
+	}
+	area.free();
 }
 
 fn create() -> Option<Index> {
@@ -125,6 +139,10 @@ fn set_shape(area: Index, shape: i32, index: Index) {
 	}
 }
 
+fn get_shape_count(area: Index) -> i32 {
+	map_or_err!(area, map_area, |area, _| area.shape_count() as i32).unwrap_or(0)
+}
+
 fn clear_shapes(area: Index) {
 	map_or_err!(area, map_area_mut, |area, _| area.remove_all_shapes());
 }
@@ -234,6 +252,50 @@ fn set_param(area: Index, param: i32, data: &Variant) {
 	}
 }
 
+fn get_param(area: Index, param: i32) -> gdnative::sys::godot_variant {
+	let result = area.map_area(|area, _| match param {
+		0 => area.gravity_force().to_variant(),
+		1 => area.gravity_direction().to_variant(),
+		2 => area.is_point_gravity().to_variant(),
+		3 => area.gravity_distance_scale().to_variant(),
+		5 => area.linear_damp().to_variant(),
+		6 => area.angular_damp().to_variant(),
+		7 => area.priority().to_variant(),
+		_ => {
+			godot_error!("Failed to get area param: {:?}", ParamError::InvalidParam);
+			Variant::new()
+		}
+	});
+
+	match result {
+		// Each Space has an imaginary Area
+		Err(IndexError::WrongType) => map_or_err!(area, map_space, |space, _| match param {
+			0 => space.gravity().length().to_variant(),
+			1 => {
+				let g = space.gravity();
+				if g == Vector3::zero() {
+					Vector3::new(0.0, -1.0, 0.0).to_variant()
+				} else {
+					g.normalize().to_variant()
+				}
+			}
+			5 => space.default_linear_damp().to_variant(),
+			6 => space.default_angular_damp().to_variant(),
+			_ => {
+				godot_error!("Parameter {} is not supported for spaces", param);
+				Variant::new()
+			}
+		})
+		.unwrap_or_else(Variant::new),
+		Err(IndexError::NoElement) => {
+			godot_error!("No area at index {:?}", area);
+			Variant::new()
+		}
+		Ok(v) => v,
+	}
+	.forget()
+}
+
 fn attach_object_instance_id(area: Index, id: i32) {
 	map_or_err!(area, map_area_mut, |area, _| area
 		.set_object_id(ObjectID::new(id as u32)));
@@ -247,6 +309,16 @@ fn get_object_instance_id(area: Index) -> i32 {
 	.unwrap_or(0) as i32
 }
 
+fn set_monitor_callback(area: Index, enable: bool) {
+	map_or_err!(area, map_area_mut, |area, _| area
+		.set_body_monitoring(enable));
+}
+
+fn set_area_monitor_callback(area: Index, enable: bool) {
+	map_or_err!(area, map_area_mut, |area, _| area
+		.set_area_monitoring(enable));
+}
+
 fn set_monitorable(area: Index, monitorable: bool) {
 	map_or_err!(area, map_area_mut, |area, _| area
 		.set_monitorable(monitorable));
@@ -311,11 +383,12 @@ fn set_collision_layer(area: Index, layer: u32) {
 
 fn get_area_event(area: Index, event: &mut ffi::PhysicsAreaMonitorEvent) -> bool {
 	map_or_err!(area, map_area_mut, |area, _| {
-		if let Some((body, intersect)) = area.pop_area_event() {
-			body.map(|body| event.set_object_id(body.object_id()))
-				.expect("Invalid body index");
-			event.set_index(Index::Area(body));
-			event.set_added(intersect);
+		if let Some(e) = area.pop_area_event() {
+			event.set_object_id(e.object_id());
+			event.set_index(Index::Area(e.index()));
+			event.set_body_shape(e.other_shape());
+			event.set_area_shape(e.self_shape());
+			event.set_added(e.intersecting());
 			true
 		} else {
 			false
@@ -326,11 +399,12 @@ fn get_area_event(area: Index, event: &mut ffi::PhysicsAreaMonitorEvent) -> bool
 
 fn get_body_event(area: Index, event: &mut ffi::PhysicsAreaMonitorEvent) -> bool {
 	map_or_err!(area, map_area_mut, |area, _| {
-		if let Some((body, intersect)) = area.pop_body_event() {
-			body.map(|body| event.set_object_id(body.object_id()))
-				.expect("Invalid body index");
-			event.set_index(Index::Body(body));
-			event.set_added(intersect);
+		if let Some(e) = area.pop_body_event() {
+			event.set_object_id(e.object_id());
+			event.set_index(Index::Body(e.index()));
+			event.set_body_shape(e.other_shape());
+			event.set_area_shape(e.self_shape());
+			event.set_added(e.intersecting());
 			true
 		} else {
 			false
@@ -338,3 +412,322 @@ fn get_body_event(area: Index, event: &mut ffi::PhysicsAreaMonitorEvent) -> bool
 	})
 	.unwrap_or(false)
 }
+
+mod call {
+	use super::super::call;
+	use super::*;
+	use ffi::{PhysicsCallError, VariantType};
+	use gdnative::prelude::*;
+
+	/// Return the wind of an area as a `Dictionary` with the fields `direction`, `strength`,
+	/// `drag`, `attenuation`, `source`, `range`, `turbulence` and `seed`, or `null` if the area
+	/// has no wind.
+	pub fn get_wind(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(area) = super::get_index(area) {
+			Ok(map_or_err!(area, map_area, |area, _| {
+				area.wind().map_or(Variant::new(), |wind| {
+					let (attenuation, source, range) = wind.attenuation();
+					let (turbulence, seed) = wind.turbulence();
+					let dict = Dictionary::new();
+					dict.insert("direction".to_variant(), wind.direction());
+					dict.insert("strength".to_variant(), wind.strength());
+					dict.insert("drag".to_variant(), wind.drag());
+					dict.insert(
+						"attenuation".to_variant(),
+						match attenuation {
+							WindAttenuation::Constant => 0,
+							WindAttenuation::Linear => 1,
+							WindAttenuation::InverseSquare => 2,
+						},
+					);
+					dict.insert("source".to_variant(), source);
+					dict.insert("range".to_variant(), range);
+					dict.insert("turbulence".to_variant(), turbulence);
+					dict.insert("seed".to_variant(), seed);
+					dict.owned_to_variant()
+				})
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Make an area push the bodies inside it towards the given wind velocity. The force applied
+	/// to a body is the difference between the wind velocity and the body's velocity multiplied
+	/// with `drag`. The optional attenuation is `0` for constant, `1` for linear and `2` for
+	/// inverse-square, starting from the local `source` point. For linear attenuation `range` is
+	/// the distance at which the wind stops, for inverse-square attenuation it is the distance
+	/// from which the wind weakens.
+	pub fn set_wind(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 3..7)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		let direction = call_get_arg!(arguments[1] => Vector3)?;
+		let strength = call_get_arg!(arguments[2] => f32)?;
+		let drag = call_get_arg!(arguments[3] => f32 || 1.0)?;
+		let attenuation = match call_get_arg!(arguments[4] => i32 || 0)? {
+			0 => WindAttenuation::Constant,
+			1 => WindAttenuation::Linear,
+			2 => WindAttenuation::InverseSquare,
+			_ => {
+				godot_error!("Invalid wind attenuation");
+				return Ok(Variant::new());
+			}
+		};
+		let source = call_get_arg!(arguments[5] => Vector3 || Vector3::zero())?;
+		let range = call_get_arg!(arguments[6] => f32 || 0.0)?;
+		if let Ok(area) = super::get_index(area) {
+			map_or_err!(area, map_area_mut, |area, _| {
+				let mut wind = Wind::new(direction, strength, drag);
+				wind.set_attenuation(attenuation, source, range);
+				if let Some((turbulence, seed)) = area.wind().map(Wind::turbulence) {
+					wind.set_turbulence(turbulence, seed);
+				}
+				area.set_wind(Some(wind));
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Set the turbulence of the wind of an area, which is the maximum random deviation of the
+	/// wind relative to its strength. The same seed gives the same turbulence. A turbulence of
+	/// `0` disables it.
+	pub fn set_wind_turbulence(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..3)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		let turbulence = call_get_arg!(arguments[1] => f32)?;
+		let seed = call_get_arg!(arguments[2] => u32 || 0)?;
+		if let Ok(area) = super::get_index(area) {
+			map_or_err!(area, map_area_mut, |area, _| {
+				if let Some(wind) = area.wind_mut() {
+					wind.set_turbulence(turbulence, seed);
+				} else {
+					godot_error!("Area has no wind");
+				}
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return the bodies that currently overlap an area as an `Array` of `Dictionary`s with the
+	/// fields `rid`, `shape` and `local_shape`. `shape` is the index of the shape of the body and
+	/// `local_shape` is the index of the shape of the area. A body overlapping with multiple
+	/// shapes is listed once for each pair of shapes.
+	pub fn get_overlapping_bodies(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(area) = super::get_index(area) {
+			Ok(map_or_err!(area, map_area, |area, _| {
+				overlapping_to_array(area.intersecting_bodies(), Index::Body)
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Return the areas that currently overlap an area as an `Array` of `Dictionary`s with the
+	/// fields `rid`, `shape` and `local_shape`. `shape` is the index of the shape of the other
+	/// area and `local_shape` is the index of the shape of the area.
+	pub fn get_overlapping_areas(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(area) = super::get_index(area) {
+			Ok(map_or_err!(area, map_area, |area, _| {
+				overlapping_to_array(area.intersecting_areas(), Index::Area)
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Converts a list of intersecting objects and shapes to an `Array` of `Dictionary`s
+	fn overlapping_to_array<I: Copy>(
+		intersecting: &[(I, u32, u32)],
+		to_index: impl Fn(I) -> Index,
+	) -> Variant {
+		let rid_key = "rid".to_variant();
+		let shape_key = "shape".to_variant();
+		let local_shape_key = "local_shape".to_variant();
+		let array = VariantArray::new();
+		for &(index, other_shape, self_shape) in intersecting {
+			let dict = Dictionary::new();
+			dict.insert(rid_key.clone(), super::get_rid(to_index(index)));
+			dict.insert(shape_key.clone(), other_shape);
+			dict.insert(local_shape_key.clone(), self_shape);
+			array.push(dict);
+		}
+		array.owned_to_variant()
+	}
+
+	/// Return the point gravity falloff of an area as a `Dictionary` with the fields `falloff`,
+	/// `radius`, `curve` and `cutoff`.
+	pub fn get_gravity_falloff(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(area) = super::get_index(area) {
+			Ok(map_or_err!(area, map_area, |area, _| {
+				let (falloff, radius, curve) = match area.gravity_falloff() {
+					GravityFalloff::DistanceScale => (0, 0.0, Float32Array::new()),
+					GravityFalloff::None => (1, 0.0, Float32Array::new()),
+					GravityFalloff::Linear { radius } => (2, *radius, Float32Array::new()),
+					GravityFalloff::InverseSquare { min_radius } => {
+						(3, *min_radius, Float32Array::new())
+					}
+					GravityFalloff::Curve { radius, samples } => {
+						(4, *radius, Float32Array::from_slice(samples))
+					}
+				};
+				let dict = Dictionary::new();
+				dict.insert("falloff".to_variant(), falloff);
+				dict.insert("radius".to_variant(), radius);
+				dict.insert("curve".to_variant(), curve);
+				dict.insert("cutoff".to_variant(), area.gravity_cutoff());
+				dict.owned_to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set how the strength of point gravity decreases with the distance to the gravity point.
+	/// The falloff is `0` for Godot's gravity distance scale, `1` for none, `2` for linear, `3` for
+	/// inverse-square and `4` for a curve. For linear falloff `radius` is the distance at which
+	/// the gravity stops, for inverse-square falloff it is the distance from which the gravity
+	/// weakens. A curve is a `PoolRealArray` of gravity multipliers evenly spaced between the
+	/// gravity point and `radius`.
+	pub fn set_gravity_falloff(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..4)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		let falloff = call_get_arg!(arguments[1] => i32)?;
+		let radius = call_get_arg!(arguments[2] => f32 || 0.0)?;
+		let curve = call_get_arg!(arguments[3] => Float32Array || Float32Array::new())?;
+		let falloff = match falloff {
+			0 => GravityFalloff::DistanceScale,
+			1 => GravityFalloff::None,
+			2 => GravityFalloff::Linear { radius },
+			3 if radius > 0.0 => GravityFalloff::InverseSquare { min_radius: radius },
+			4 if radius > 0.0 && curve.len() > 0 => GravityFalloff::Curve {
+				radius,
+				samples: curve.read().to_vec(),
+			},
+			3 | 4 => {
+				godot_error!("Invalid gravity falloff radius or curve");
+				return Ok(Variant::new());
+			}
+			_ => {
+				godot_error!("Invalid gravity falloff");
+				return Ok(Variant::new());
+			}
+		};
+		if let Ok(area) = super::get_index(area) {
+			map_or_err!(area, map_area_mut, |area, _| area
+				.set_gravity_falloff(falloff));
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Set the distance from the gravity point beyond which the area doesn't override the gravity
+	/// and damp of bodies at all. A distance of `0` removes the limit.
+	pub fn set_gravity_cutoff(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		let cutoff = call_get_arg!(arguments[1] => f32)?;
+		if let Ok(area) = super::get_index(area) {
+			map_or_err!(area, map_area_mut, |area, _| area
+				.set_gravity_cutoff(cutoff));
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return the fluid of an area as a `Dictionary` with the fields `density`, `surface`,
+	/// `linear_drag` and `angular_drag`, or `null` if the area has no fluid.
+	pub fn get_fluid(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(area) = super::get_index(area) {
+			Ok(map_or_err!(area, map_area, |area, _| {
+				area.fluid().map_or(Variant::new(), |fluid| {
+					let dict = Dictionary::new();
+					dict.insert("density".to_variant(), fluid.density());
+					dict.insert("surface".to_variant(), fluid.surface());
+					dict.insert("linear_drag".to_variant(), fluid.linear_drag());
+					dict.insert("angular_drag".to_variant(), fluid.angular_drag());
+					dict.owned_to_variant()
+				})
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Make an area behave like a fluid with the given density. Bodies below the surface, which
+	/// is a `Plane` in local space with the normal pointing out of the fluid, float up. The
+	/// optional linear and angular drag slow down bodies in proportion to how much of them is
+	/// submerged.
+	pub fn set_fluid(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 3..5)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		let density = call_get_arg!(arguments[1] => f32)?;
+		let surface = call_get_arg!(arguments[2] => Plane)?;
+		let linear_drag = call_get_arg!(arguments[3] => f32 || 0.0)?;
+		let angular_drag = call_get_arg!(arguments[4] => f32 || 0.0)?;
+		if let Ok(area) = super::get_index(area) {
+			map_or_err!(area, map_area_mut, |area, _| {
+				area.set_fluid(Some(Fluid::new(
+					density,
+					surface,
+					linear_drag,
+					angular_drag,
+				)));
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Remove the fluid of an area.
+	pub fn clear_fluid(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(area) = super::get_index(area) {
+			map_or_err!(area, map_area_mut, |area, _| area.set_fluid(None));
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Remove the wind of an area.
+	pub fn clear_wind(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let area = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(area) = super::get_index(area) {
+			map_or_err!(area, map_area_mut, |area, _| area.set_wind(None));
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+}
+
+pub(super) use call::*;
@@ -1,6 +1,6 @@
 use super::index::BodyIndex;
 use super::*;
-use crate::body::Body;
+use crate::body::{Body, GravityRule};
 use crate::util::*;
 use gdnative::core_types::*;
 use gdnative::godot_error;
@@ -147,20 +147,64 @@ pub fn init(ffi: &mut ffi::FFI) {
 		body_attach_object_instance_id,
 		attach_object_instance_id
 	);
+	ffi!(ffi, body_clear_shapes, clear_shapes);
 	ffi!(ffi, body_create, create);
 	ffi!(
 		ffi,
 		body_is_continuous_collision_detection_enabled,
 		is_continuous_collision_detection_enabled
 	);
+	ffi!(ffi, body_get_collision_exception, get_collision_exception);
+	ffi!(
+		ffi,
+		body_get_collision_exception_count,
+		get_collision_exception_count
+	);
+	ffi!(ffi, body_get_collision_layer, get_collision_layer);
+	ffi!(ffi, body_get_collision_mask, get_collision_mask);
 	ffi!(ffi, body_get_contact, get_contact);
+	ffi!(
+		ffi,
+		body_get_contacts_reported_depth_threshold,
+		get_contacts_reported_depth_threshold
+	);
 	ffi!(ffi, body_get_direct_state, get_direct_state);
 	ffi!(ffi, body_get_kinematic_safe_margin, |_| 0.0);
+	ffi!(
+		ffi,
+		body_get_max_contacts_reported,
+		get_max_contacts_reported
+	);
+	ffi!(ffi, body_get_mode, get_mode);
+	ffi!(ffi, body_get_object_instance_id, get_object_instance_id);
+	ffi!(ffi, body_get_param, get_param);
+	ffi!(ffi, body_get_shape, get_shape);
+	ffi!(ffi, body_get_shape_count, get_shape_count);
+	ffi!(ffi, body_get_shape_transform, get_shape_transform);
+	ffi!(ffi, body_get_space, get_space);
+	ffi!(ffi, body_get_state, get_state);
+	ffi!(ffi, body_get_user_flags, get_user_flags);
 	ffi!(ffi, body_is_axis_locked, is_axis_locked);
+	ffi!(
+		ffi,
+		body_is_omitting_force_integration,
+		is_omitting_force_integration
+	);
+	ffi!(ffi, body_is_ray_pickable, is_ray_pickable);
+	ffi!(
+		ffi,
+		body_remove_collision_exception,
+		remove_collision_exception
+	);
 	ffi!(ffi, body_remove_shape, remove_shape);
 	ffi!(ffi, body_set_axis_lock, set_axis_lock);
 	ffi!(ffi, body_set_collision_layer, set_collision_layer);
 	ffi!(ffi, body_set_collision_mask, set_collision_mask);
+	ffi!(
+		ffi,
+		body_set_contacts_reported_depth_threshold,
+		set_contacts_reported_depth_threshold
+	);
 	ffi!(
 		ffi,
 		body_set_enable_continuous_collision_detection,
@@ -179,15 +223,29 @@ pub fn init(ffi: &mut ffi::FFI) {
 		set_omit_force_integration
 	);
 	ffi!(ffi, body_set_param, set_param);
+	ffi!(ffi, body_set_shape, set_shape);
 	ffi!(ffi, body_set_shape_transform, set_shape_transform);
 	ffi!(ffi, body_set_shape_disabled, set_shape_disabled);
 	ffi!(ffi, body_set_space, set_space);
 	ffi!(ffi, body_set_state, set_state);
+	ffi!(ffi, body_set_user_flags, set_user_flags);
 	ffi!(ffi, body_set_ray_pickable, set_ray_pickable);
 }
 
 /// Frees the given body, removing it from it's attached space (if any)
 pub fn free(body: Body) {
+	let index = body.index();
+	let mut bodies = BodyIndex::write_all();
+	for &other in body.exclusions() {
+		if let Some(other) = bodies.get_mut(other.into()) {
+			other.forget_exclusion(index);
+		}
+	}
+	drop(bodies);
+	let object_id = body.object_id();
+	for (_, area) in AreaIndex::write_all().iter_mut() {
+		area.remove_freed_body(index, object_id);
+	}
 	body.free()
 }
 
@@ -228,6 +286,48 @@ fn add_collision_exception(body_a: Index, body_b: Index) {
 	}
 }
 
+fn remove_collision_exception(body_a: Index, body_b: Index) {
+	if body_a == body_b {
+		return; // Ditto
+	}
+	if let Some(body_a) = body_a.as_body() {
+		if let Some(body_b) = body_b.as_body() {
+			let mut bodies = BodyIndex::write_all();
+			let (body_a, body_b) = bodies.get2_mut(body_a.into(), body_b.into());
+			if let Some(body_a) = body_a {
+				if let Some(body_b) = body_b {
+					// Godot doesn't complain about removing nonexistent exceptions either
+					let _ = body_a.remove_exclusion(body_b);
+				} else {
+					godot_error!("No body at index B");
+				}
+			} else {
+				godot_error!("No body at index A");
+			}
+		} else {
+			godot_error!("Index B does not point to a body");
+		}
+	} else {
+		godot_error!("Index A does not point to a body");
+	}
+}
+
+fn get_collision_exception(body: Index, index: i32) -> Option<Index> {
+	map_or_err!(body, map_body, |body, _| {
+		if let Some(&other) = body.exclusions().get(index as usize) {
+			Some(Index::Body(other))
+		} else {
+			godot_error!("Invalid exception index");
+			None
+		}
+	})
+	.unwrap_or(None)
+}
+
+fn get_collision_exception_count(body: Index) -> i32 {
+	map_or_err!(body, map_body, |body, _| body.exclusions().len() as i32).unwrap_or(0)
+}
+
 fn add_central_force(body: Index, force: &Vector3) {
 	map_or_err!(body, map_body_mut, |body, _| {
 		body.add_central_force(*force);
@@ -267,10 +367,16 @@ fn attach_object_instance_id(body: Index, id: u32) {
 		.set_object_id(ObjectID::new(id)));
 }
 
+fn get_object_instance_id(body: Index) -> u32 {
+	map_or_err!(body, map_body, |b, _| b.object_id().map(ObjectID::get))
+		.flatten()
+		.unwrap_or(0)
+}
+
 fn get_direct_state(body: Index, state: &mut ffi::PhysicsBodyState) {
 	map_or_err!(body, map_body, |body, _| {
 		body.read_body(|rb, space| {
-			state.set_transform(&isometry_to_transform(rb.position()));
+			state.set_transform(&isometry_to_transform(Body::position_of(rb)));
 			state.set_linear_velocity(vec_na_to_gd(*rb.linvel()));
 			state.set_angular_velocity(vec_na_to_gd(*rb.angvel()));
 			state.set_sleeping(rb.is_sleeping());
@@ -283,6 +389,7 @@ fn get_direct_state(body: Index, state: &mut ffi::PhysicsBodyState) {
 			let inv_inertia_tensor = mp.reconstruct_inverse_inertia_matrix();
 			state.set_inv_inertia_tensor(&mat3_to_basis(&inv_inertia_tensor));
 			state.set_contact_count(body.contact_count());
+			state.set_dominance_group(rb.dominance_group());
 			state.set_space(space.map(Index::Space));
 		});
 	});
@@ -296,9 +403,14 @@ fn get_contact(body: Index, id: u32, contact: &mut ffi::PhysicsBodyContact) {
 				contact.set_local_position(c.local_position(rb));
 				contact.set_local_normal(c.local_normal(rb));
 				contact.set_position(c.position());
+				contact.set_velocity(c.other_velocity());
 				contact.set_object_id(c.object_id());
 				contact.set_shape(c.other_shape());
 				contact.set_local_shape(c.self_shape());
+				contact.set_impulse(c.impulse());
+				contact.set_depth(c.depth());
+				contact.set_relative_velocity(c.relative_velocity());
+				contact.set_user_flags(c.other_user_flags());
 			});
 		} else {
 			godot_error!("Invalid contact");
@@ -306,6 +418,45 @@ fn get_contact(body: Index, id: u32, contact: &mut ffi::PhysicsBodyContact) {
 	});
 }
 
+fn get_shape(body: Index, shape: i32) -> Option<Index> {
+	map_or_err!(body, map_body, |body, _| {
+		if let Some(shape) = body.get_shape_index(shape as u32) {
+			Some(Index::Shape(shape))
+		} else {
+			godot_error!("Invalid shape index");
+			None
+		}
+	})
+	.unwrap_or(None)
+}
+
+fn get_shape_count(body: Index) -> i32 {
+	map_or_err!(body, map_body, |body, _| body.shape_count() as i32).unwrap_or(0)
+}
+
+// FIXME handle to_sys() stuff in the generated ffi wrapper
+fn get_shape_transform(body: Index, shape: i32) -> gdnative::sys::godot_transform {
+	let transform = map_or_err!(body, map_body, |body, _| {
+		body.get_shape_transform(shape as u32).unwrap_or_else(|| {
+			godot_error!("Invalid shape index");
+			Transform {
+				basis: Basis::identity(),
+				origin: Vector3::zero(),
+			}
+		})
+	});
+	let transform = transform.unwrap_or(Transform {
+		basis: Basis::identity(),
+		origin: Vector3::zero(),
+	});
+	// SAFETY: transform is guaranteed valid
+	unsafe { *transform.sys() }
+}
+
+fn clear_shapes(body: Index) {
+	map_or_err!(body, map_body_mut, |body, _| body.clear_shapes());
+}
+
 fn remove_shape(body: Index, shape: i32) {
 	map_or_err!(body, map_body_mut, |body, _| body
 		.remove_shape(shape as u32));
@@ -328,6 +479,30 @@ fn set_param(body: Index, param: i32, value: f32) {
 	};
 }
 
+fn get_param(body: Index, param: i32) -> f32 {
+	map_or_err!(body, map_body, |body, _| match param {
+		0 => body.restitution(),
+		1 => body.friction(),
+		2 => body.mass(),
+		3 => body.gravity_scale(),
+		4 => body.linear_damp(),
+		5 => body.angular_damp(),
+		_ => {
+			godot_error!("Invalid param");
+			0.0
+		}
+	})
+	.unwrap_or(0.0)
+}
+
+fn get_collision_layer(body: Index) -> u32 {
+	map_or_err!(body, map_body, |body, _| body.groups()).unwrap_or(0)
+}
+
+fn get_collision_mask(body: Index) -> u32 {
+	map_or_err!(body, map_body, |body, _| body.mask()).unwrap_or(0)
+}
+
 fn set_collision_layer(body: Index, layer: u32) {
 	map_or_err!(body, map_body_mut, |body, _| body.set_groups(layer));
 }
@@ -336,29 +511,60 @@ fn set_collision_mask(body: Index, mask: u32) {
 	map_or_err!(body, map_body_mut, |body, _| body.set_mask(mask));
 }
 
+/// Returns the mode of the body. `BODY_MODE_CHARACTER` is never returned: `set_mode` refuses it
+/// and the body keeps the mode it had before, which is what this returns.
+fn get_mode(body: Index) -> i32 {
+	map_or_err!(body, map_body, |body, _| match body.body_type() {
+		RigidBodyType::Static => 0,
+		RigidBodyType::KinematicPositionBased | RigidBodyType::KinematicVelocityBased => 1,
+		RigidBodyType::Dynamic => 2,
+	})
+	.unwrap_or(2)
+}
+
 fn set_mode(body: Index, mode: i32) {
 	match Mode::new(mode) {
 		Ok(mode) => {
-			let mode = match mode {
-				Mode::Static => RigidBodyType::Static,
-				Mode::Kinematic => RigidBodyType::KinematicPositionBased,
-				Mode::Rigid => RigidBodyType::Dynamic,
-				Mode::Character => {
-					godot_error!("Character mode is not supported");
-					return;
-				}
-			};
-			map_or_err!(body, map_body_mut, |body, _| body.set_body_type(mode));
+			map_or_err!(body, map_body_mut, |body, _| {
+				let mode = match mode {
+					Mode::Static => RigidBodyType::Static,
+					Mode::Kinematic => body.kinematic_type(),
+					Mode::Rigid => RigidBodyType::Dynamic,
+					Mode::Character => {
+						godot_error!("Character mode is not supported");
+						return;
+					}
+				};
+				body.set_body_type(mode);
+			});
 		}
 		Err(_) => godot_error!("Invalid mode"),
 	}
 }
 
+fn is_omitting_force_integration(body: Index) -> bool {
+	map_or_err!(body, map_body, |body, _| body
+		.is_omitting_force_integration())
+	.unwrap_or(false)
+}
+
 fn set_omit_force_integration(body: Index, enable: bool) {
 	map_or_err!(body, map_body_mut, |body, _| body
 		.set_omit_force_integration(enable));
 }
 
+fn set_shape(body: Index, shape: i32, index: Index) {
+	if let Index::Shape(index) = index {
+		map_or_err!(body, map_body_mut, |body, _| {
+			if body.set_shape(shape as u32, index).is_err() {
+				godot_error!("Invalid shape index");
+			}
+		});
+	} else {
+		godot_error!("Index does not point to a shape");
+	}
+}
+
 fn set_shape_transform(body: Index, shape: i32, transform: &Transform) {
 	let shape = shape as u32;
 	map_or_err!(body, map_body_mut, |body, _| body
@@ -370,6 +576,10 @@ fn set_shape_disabled(body: Index, shape: i32, disable: bool) {
 		.set_shape_enable(shape as u32, !disable));
 }
 
+fn get_space(body: Index) -> Option<Index> {
+	map_or_err!(body, map_body, |body, _| body.space().map(Index::Space)).unwrap_or(None)
+}
+
 fn set_space(body: Index, space: Option<Index>) {
 	map_or_err!(body, map_body_mut, |body, _| {
 		if let Some(space) = space {
@@ -380,6 +590,18 @@ fn set_space(body: Index, space: Option<Index>) {
 	});
 }
 
+fn get_user_flags(body: Index) -> u32 {
+	map_or_err!(body, map_body, |body, _| body.user_flags()).unwrap_or(0)
+}
+
+fn set_user_flags(body: Index, flags: u32) {
+	map_or_err!(body, map_body_mut, |body, _| body.set_user_flags(flags));
+}
+
+fn is_ray_pickable(body: Index) -> bool {
+	map_or_err!(body, map_body, |body, _| body.ray_pickable()).unwrap_or(false)
+}
+
 fn set_ray_pickable(body: Index, enable: bool) {
 	map_or_err!(body, map_body_mut, |body, _| body.set_ray_pickable(enable));
 }
@@ -388,7 +610,7 @@ fn set_state(body: Index, state: i32, value: &Variant) {
 	map_or_err!(body, map_body_mut, |body, _| {
 		match State::new(state, value) {
 			Ok(state) => match state {
-				State::Transform(trf) => body.set_transform(&trf),
+				State::Transform(trf) => body.set_transform(&trf, false),
 				State::LinearVelocity(vel) => body.set_linear_velocity(vel),
 				State::AngularVelocity(vel) => body.set_angular_velocity(vel),
 				State::Sleeping(sleep) => body.set_sleeping(sleep),
@@ -403,11 +625,40 @@ fn set_state(body: Index, state: i32, value: &Variant) {
 	});
 }
 
+fn get_state(body: Index, state: i32) -> gdnative::sys::godot_variant {
+	map_or_err!(body, map_body, |body, _| match state {
+		0 => body.transform().to_variant(),
+		1 => body.linear_velocity().to_variant(),
+		2 => body.angular_velocity().to_variant(),
+		3 => body.is_sleeping().to_variant(),
+		4 => body.can_sleep().to_variant(),
+		_ => {
+			godot_error!("Invalid state: {:?}", StateError::InvalidType);
+			Variant::new()
+		}
+	})
+	.unwrap_or_else(Variant::new)
+	.forget()
+}
+
+fn get_max_contacts_reported(body: Index) -> i32 {
+	map_or_err!(body, map_body, |body, _| body.max_contacts() as i32).unwrap_or(0)
+}
+
 fn set_max_contacts_reported(body: Index, count: i32) {
 	let count = count as u32;
 	map_or_err!(body, map_body_mut, |body, _| body.set_max_contacts(count));
 }
 
+fn get_contacts_reported_depth_threshold(body: Index) -> f32 {
+	map_or_err!(body, map_body, |body, _| body.contacts_depth_threshold()).unwrap_or(0.0)
+}
+
+fn set_contacts_reported_depth_threshold(body: Index, threshold: f32) {
+	map_or_err!(body, map_body_mut, |body, _| body
+		.set_contacts_depth_threshold(threshold));
+}
+
 fn is_continuous_collision_detection_enabled(body: Index) -> bool {
 	map_or_err!(body, map_body, |body, _| body.is_ccd_enabled()).unwrap_or(false)
 }
@@ -420,7 +671,7 @@ fn set_axis_lock(body: Index, axis: i32, lock: bool) {
 	if let Ok(axis) = BodyAxis::new(axis) {
 		map_or_err!(body, map_body_mut, |body, _| {
 			match axis {
-				BodyAxis::Linear(_) => body.set_translation_lock(lock),
+				BodyAxis::Linear(axis) => body.set_translation_lock(axis, lock),
 				BodyAxis::Angular(axis) => body.set_rotation_lock(axis, lock),
 			}
 		});
@@ -433,7 +684,7 @@ fn is_axis_locked(body: Index, axis: i32) -> bool {
 	if let Ok(axis) = BodyAxis::new(axis) {
 		map_or_err!(body, map_body, |body, _| {
 			match axis {
-				BodyAxis::Linear(_) => body.is_translation_locked(),
+				BodyAxis::Linear(axis) => body.is_translation_locked(axis),
 				BodyAxis::Angular(axis) => body.is_rotation_locked(axis),
 			}
 		})
@@ -514,6 +765,410 @@ mod call {
 		}
 		Ok(Variant::new())
 	}
+
+	/// Return the principal inertia.
+	pub fn get_principal_inertia(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.principal_inertia().owned_to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Return the local frame of the principal inertia.
+	pub fn get_inertia_frame(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.inertia_frame().owned_to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set a custom principal inertia and optionally it's local frame instead of deriving it
+	/// from the shapes. The frame is orthonormalized and must be a rotation, i.e. have a positive
+	/// determinant.
+	pub fn set_custom_inertia(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..3)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let inertia = call_get_arg!(arguments[1] => Vector3)?;
+		let frame = call_get_arg!(arguments[2] => Basis || Basis::identity())?;
+		if basis_to_mat3(&frame).determinant() <= 0.0 {
+			godot_error!("Inertia frame is not a rotation");
+			return Ok(Variant::new());
+		}
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				body.set_custom_inertia(Some((inertia, frame)));
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Derive the inertia from the shapes again.
+	pub fn clear_custom_inertia(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| body.set_custom_inertia(None));
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return whether the mass is derived from the density of the shapes.
+	pub fn is_mass_from_density(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.is_mass_from_density().to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set whether the mass is derived from the density of the shapes instead of the mass
+	/// parameter.
+	pub fn set_mass_from_density(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let enable = call_get_arg!(arguments[1] => bool)?;
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				body.set_mass_from_density(enable);
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return the density of a shape.
+	pub fn get_shape_density(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let shape = call_get_arg!(arguments[1] => u32)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.shape_density(shape).map_or_else(
+					|_| {
+						godot_error!("Invalid shape index");
+						Variant::new()
+					},
+					|density| density.to_variant(),
+				)
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set the density of a shape. This is only used if the mass is derived from the density.
+	pub fn set_shape_density(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 3..3)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let shape = call_get_arg!(arguments[1] => u32)?;
+		let density = call_get_arg!(arguments[2] => f32)?;
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				if body.set_shape_density(shape, density).is_err() {
+					godot_error!("Invalid shape index");
+				}
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return the transform of a body before the last step.
+	pub fn get_previous_transform(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.previous_transform().owned_to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Return the transform of a body interpolated between the transform before and after the
+	/// last step. This is useful for smooth rendering when the framerate is higher than the
+	/// physics tick rate, in which case `alpha` is `Engine.get_physics_interpolation_fraction()`.
+	pub fn get_interpolated_transform(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let alpha = call_get_arg!(arguments[1] => f32)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.interpolated_transform(alpha).owned_to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Return the custom gravity, or `null` if the body uses the gravity of the space.
+	pub fn get_gravity(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.custom_gravity()
+					.map_or(Variant::new(), |(g, _)| g.owned_to_variant())
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Return how the custom gravity combines with the gravity of areas. `0` means areas replace
+	/// or add to it as they do to the gravity of the space, `1` means areas always add to it and
+	/// `2` means areas are ignored.
+	pub fn get_gravity_rule(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.custom_gravity().map_or(Variant::new(), |(_, rule)| {
+					match rule {
+						GravityRule::Replace => 0,
+						GravityRule::Add => 1,
+						GravityRule::IgnoreAreas => 2,
+					}
+					.to_variant()
+				})
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set a custom gravity that is used instead of the gravity of the space. The optional rule
+	/// determines how it combines with the gravity of areas, see `body_get_gravity_rule`.
+	pub fn set_gravity(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..3)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let gravity = call_get_arg!(arguments[1] => Vector3)?;
+		let rule = match call_get_arg!(arguments[2] => i32 || 0)? {
+			0 => GravityRule::Replace,
+			1 => GravityRule::Add,
+			2 => GravityRule::IgnoreAreas,
+			_ => {
+				godot_error!("Invalid gravity rule");
+				return Ok(Variant::new());
+			}
+		};
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				body.set_custom_gravity(Some((gravity, rule)));
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Make a body use the gravity of the space again.
+	pub fn clear_gravity(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| body.set_custom_gravity(None));
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return the dominance group.
+	pub fn get_dominance_group(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				i64::from(body.dominance_group()).to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set the dominance group. Bodies in a higher group are not affected by bodies in a lower
+	/// group. The group must be between -127 and 127.
+	pub fn set_dominance_group(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let group = call_get_arg!(arguments[1] => i64)?;
+		if !(-127..=127).contains(&group) {
+			godot_error!("Dominance group must be between -127 and 127");
+			return Ok(Variant::new());
+		}
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				body.set_dominance_group(group as i8);
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return whether a body is moved by setting it's velocity when it is kinematic.
+	pub fn is_kinematic_velocity_based(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 1..1)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				body.is_kinematic_velocity_based().to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set whether a body is moved by setting it's velocity instead of it's transform when it is
+	/// kinematic.
+	pub fn set_kinematic_velocity_based(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let enable = call_get_arg!(arguments[1] => bool)?;
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				body.set_kinematic_velocity_based(enable);
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Move a body to the given transform without giving it any velocity. Position-based kinematic
+	/// bodies are otherwise moved during the next step with the velocity needed to get there.
+	pub fn teleport(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let transform = call_get_arg!(arguments[1] => Transform)?;
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				body.set_transform(&transform, true);
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return the material of a shape as an array with the friction, bounce, rough and absorbent
+	/// properties, or `null` if the shape uses the material of the body.
+	pub fn get_shape_material(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let shape = call_get_arg!(arguments[1] => u32)?;
+		if let Ok(body) = super::get_index(body) {
+			Ok(map_or_err!(body, map_body, |body, _| {
+				match body.shape_material(shape) {
+					Ok(Some((friction, bounce))) => {
+						let array = VariantArray::new();
+						array.push(friction.abs());
+						array.push(bounce.abs());
+						array.push(friction.is_sign_negative());
+						array.push(bounce.is_sign_negative());
+						array.owned_to_variant()
+					}
+					Ok(None) => Variant::new(),
+					Err(_) => {
+						godot_error!("Invalid shape index");
+						Variant::new()
+					}
+				}
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set the material of a shape, overriding the friction and bounce of the body.
+	///
+	/// Like with Godot, rough shapes use their own friction instead of the lowest one and
+	/// absorbent shapes subtract their bounce instead of adding it.
+	pub fn set_shape_material(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 4..6)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let shape = call_get_arg!(arguments[1] => u32)?;
+		let friction = call_get_arg!(arguments[2] => f32)?;
+		let bounce = call_get_arg!(arguments[3] => f32)?;
+		let rough = call_get_arg!(arguments[4] => bool || false)?;
+		let absorbent = call_get_arg!(arguments[5] => bool || false)?;
+		let friction = if rough { -friction } else { friction };
+		let bounce = if absorbent { -bounce } else { bounce };
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				if body
+					.set_shape_material(shape, Some((friction, bounce)))
+					.is_err()
+				{
+					godot_error!("Invalid shape index");
+				}
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Make a shape use the material of the body again.
+	pub fn clear_shape_material(arguments: &[&Variant]) -> call::Result {
+		call_check_arg_count!(arguments in 2..2)?;
+		let body = call_get_arg!(arguments[0] => Rid)?;
+		let shape = call_get_arg!(arguments[1] => u32)?;
+		if let Ok(body) = super::get_index(body) {
+			map_or_err!(body, map_body_mut, |body, _| {
+				if body.set_shape_material(shape, None).is_err() {
+					godot_error!("Invalid shape index");
+				}
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
 }
 
 pub(super) use call::*;
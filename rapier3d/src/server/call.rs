@@ -42,18 +42,54 @@ macro_rules! call_get_arg {
 	($args:ident[$index:literal] => u32 || $default:expr) => {
 		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_i64, Int, $default).map(|v| v as u32)
 	};
+	// GDScript doesn't convert integer literals to reals when passing them to `call`, so accept
+	// both
+	($args:ident[$index:literal] => f32) => {
+		call_get_arg!(@INTERNAL $args, $index, try_to_f64, Real)
+			.or_else(|e| $args[$index].try_to_i64().map(|v| v as f64).ok_or(e))
+			.map(|v| v as f32)
+	};
+	($args:ident[$index:literal] => f32 || $default:expr) => {
+		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_f64, Real, $default)
+			.or_else(|e| $args[$index].try_to_i64().map(|v| v as f64).ok_or(e))
+			.map(|v| v as f32)
+	};
 	($args:ident[$index:literal] => Vector3) => {
 		call_get_arg!(@INTERNAL $args, $index, try_to_vector3, Vector3)
 	};
 	($args:ident[$index:literal] => Vector3 || $default:expr) => {
 		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_vector3, Vector3, $default)
 	};
+	($args:ident[$index:literal] => Plane) => {
+		call_get_arg!(@INTERNAL $args, $index, try_to_plane, Plane)
+	};
+	($args:ident[$index:literal] => Plane || $default:expr) => {
+		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_plane, Plane, $default)
+	};
+	($args:ident[$index:literal] => Basis) => {
+		call_get_arg!(@INTERNAL $args, $index, try_to_basis, Basis)
+	};
+	($args:ident[$index:literal] => Basis || $default:expr) => {
+		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_basis, Basis, $default)
+	};
+	($args:ident[$index:literal] => Transform) => {
+		call_get_arg!(@INTERNAL $args, $index, try_to_transform, Transform)
+	};
+	($args:ident[$index:literal] => Transform || $default:expr) => {
+		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_transform, Transform, $default)
+	};
 	($args:ident[$index:literal] => VariantArray) => {
 		call_get_arg!(@INTERNAL $args, $index, try_to_array, Array)
 	};
 	($args:ident[$index:literal] => VariantArray || $default:expr) => {
 		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_array, Array, $default)
 	};
+	($args:ident[$index:literal] => Float32Array) => {
+		call_get_arg!(@INTERNAL $args, $index, try_to_float32_array, PoolRealArray)
+	};
+	($args:ident[$index:literal] => Float32Array || $default:expr) => {
+		call_get_arg!(@INTERNAL @maybe $args, $index, try_to_float32_array, PoolRealArray, $default)
+	};
 	($args:ident[$index:literal] => Rid) => {
 		call_get_arg!(@INTERNAL $args, $index, try_to_rid, Rid)
 	};
@@ -100,6 +136,44 @@ pub(super) fn call(
 		wch!("body_set_local_com") => body::set_local_com(arguments),
 		wch!("body_add_local_force") => body::add_local_force(arguments),
 		wch!("body_add_local_impulse") => body::add_local_impulse(arguments),
+		wch!("body_get_principal_inertia") => body::get_principal_inertia(arguments),
+		wch!("body_get_inertia_frame") => body::get_inertia_frame(arguments),
+		wch!("body_set_custom_inertia") => body::set_custom_inertia(arguments),
+		wch!("body_clear_custom_inertia") => body::clear_custom_inertia(arguments),
+		wch!("body_is_mass_from_density") => body::is_mass_from_density(arguments),
+		wch!("body_set_mass_from_density") => body::set_mass_from_density(arguments),
+		wch!("body_get_shape_density") => body::get_shape_density(arguments),
+		wch!("body_set_shape_density") => body::set_shape_density(arguments),
+		wch!("body_get_previous_transform") => body::get_previous_transform(arguments),
+		wch!("body_get_interpolated_transform") => body::get_interpolated_transform(arguments),
+		wch!("body_get_gravity") => body::get_gravity(arguments),
+		wch!("body_get_gravity_rule") => body::get_gravity_rule(arguments),
+		wch!("body_set_gravity") => body::set_gravity(arguments),
+		wch!("body_clear_gravity") => body::clear_gravity(arguments),
+		wch!("body_get_dominance_group") => body::get_dominance_group(arguments),
+		wch!("body_set_dominance_group") => body::set_dominance_group(arguments),
+		wch!("body_is_kinematic_velocity_based") => body::is_kinematic_velocity_based(arguments),
+		wch!("body_set_kinematic_velocity_based") => body::set_kinematic_velocity_based(arguments),
+		wch!("body_teleport") => body::teleport(arguments),
+		wch!("body_get_shape_material") => body::get_shape_material(arguments),
+		wch!("body_set_shape_material") => body::set_shape_material(arguments),
+		wch!("body_clear_shape_material") => body::clear_shape_material(arguments),
+		wch!("area_get_wind") => area::get_wind(arguments),
+		wch!("area_set_wind") => area::set_wind(arguments),
+		wch!("area_set_wind_turbulence") => area::set_wind_turbulence(arguments),
+		wch!("area_clear_wind") => area::clear_wind(arguments),
+		wch!("area_get_overlapping_bodies") => area::get_overlapping_bodies(arguments),
+		wch!("area_get_overlapping_areas") => area::get_overlapping_areas(arguments),
+		wch!("area_get_gravity_falloff") => area::get_gravity_falloff(arguments),
+		wch!("area_set_gravity_falloff") => area::set_gravity_falloff(arguments),
+		wch!("area_set_gravity_cutoff") => area::set_gravity_cutoff(arguments),
+		wch!("area_get_fluid") => area::get_fluid(arguments),
+		wch!("area_set_fluid") => area::set_fluid(arguments),
+		wch!("area_clear_fluid") => area::clear_fluid(arguments),
+		wch!("space_get_max_ccd_substeps") => space::get_max_ccd_substeps(arguments),
+		wch!("space_set_max_ccd_substeps") => space::set_max_ccd_substeps(arguments),
+		wch!("space_get_ccd_prediction_distance") => space::get_ccd_prediction_distance(arguments),
+		wch!("space_set_ccd_prediction_distance") => space::set_ccd_prediction_distance(arguments),
 		wch!("space_intersections_with_ray") => space::intersections_with_ray(arguments),
 		_ => Err(ffi::PhysicsCallError::InvalidMethod),
 	})
@@ -86,7 +86,7 @@ pub enum PhysicsCallError {
 		max: u8,
 	},
 	TooFewArguments {
-		min: u8,	
+		min: u8,
 	},
 	InstanceIsNull,
 }
@@ -189,6 +189,10 @@ impl PhysicsBodyState {
 		self.angular_velocity = velocity.to_sys();
 	}
 
+	pub fn set_dominance_group(&mut self, group: i8) {
+		self.dominance_group = group;
+	}
+
 	pub fn set_inv_inertia(&mut self, inv_inertia: Vector3) {
 		self.inv_inertia = inv_inertia.to_sys();
 	}
@@ -242,6 +246,10 @@ impl PhysicsBodyContact {
 		self.position = position.to_sys();
 	}
 
+	pub fn set_velocity(&mut self, velocity: Vector3) {
+		self.velocity = velocity.to_sys();
+	}
+
 	pub fn set_object_id(&mut self, id: Option<ObjectID>) {
 		self.object_id = id.map(ObjectID::get).unwrap_or(0) as i32;
 	}
@@ -265,6 +273,18 @@ impl PhysicsBodyContact {
 	pub fn set_impulse(&mut self, impulse: f32) {
 		self.impulse = impulse;
 	}
+
+	pub fn set_depth(&mut self, depth: f32) {
+		self.depth = depth;
+	}
+
+	pub fn set_relative_velocity(&mut self, velocity: Vector3) {
+		self.relative_velocity = velocity.to_sys();
+	}
+
+	pub fn set_user_flags(&mut self, flags: u32) {
+		self.user_flags = flags;
+	}
 }
 
 impl PhysicsRayResult {
@@ -287,6 +307,10 @@ impl PhysicsRayResult {
 	pub fn set_shape(&mut self, index: u32) {
 		self.shape = index as i32;
 	}
+
+	pub fn set_user_flags(&mut self, flags: u32) {
+		self.user_flags = flags;
+	}
 }
 
 impl PhysicsRayInfo {
@@ -379,6 +403,14 @@ impl PhysicsAreaMonitorEvent {
 		self.id = index.raw();
 	}
 
+	pub fn set_body_shape(&mut self, shape: u32) {
+		self.body_shape = shape;
+	}
+
+	pub fn set_area_shape(&mut self, shape: u32) {
+		self.area_shape = shape;
+	}
+
 	pub fn set_added(&mut self, added: bool) {
 		self.added = added;
 	}
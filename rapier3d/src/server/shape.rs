@@ -6,6 +6,10 @@ use gdnative::core_types::*;
 use rapier3d::geometry::{Collider, ColliderBuilder, SharedShape};
 use rapier3d::math::Point;
 use rapier3d::na::{DMatrix, Dynamic, Isometry3, Matrix, Matrix3x1, Point3};
+use std::sync::Mutex;
+
+/// The maximum amount of differently scaled variants of a shape to keep around
+const MAX_CACHED_SCALED_SHAPES: usize = 16;
 
 #[derive(Copy, Clone, Debug)]
 enum Type {
@@ -47,6 +51,7 @@ pub struct Shape {
 	r#type: Type,
 	shape: SharedShape,
 	index: Option<ShapeIndex>,
+	scaled_cache: Mutex<Vec<([u32; 3], SharedShape)>>,
 }
 
 #[derive(Debug)]
@@ -102,6 +107,7 @@ impl Shape {
 			r#type,
 			shape,
 			index,
+			scaled_cache: Mutex::new(Vec::new()),
 		}
 	}
 
@@ -113,6 +119,7 @@ impl Shape {
 		let dict = || e(data.try_to_dictionary());
 		let get_f = |d: &Dictionary, k| Ok(e(d.get(k).try_to_f64())? as f32);
 		let get_i = |d: &Dictionary, k| e(d.get(k).try_to_i64());
+		self.scaled_cache.get_mut().unwrap().clear();
 		self.shape = match self.r#type {
 			Type::Box => {
 				let extents = e(data.try_to_vector3())?;
@@ -278,7 +285,31 @@ impl Shape {
 	}
 
 	/// Do a best effort to scale a collider appropriately
+	///
+	/// Scaled shapes are cached, so scaling with the same scale again is cheap.
 	pub fn scaled(&self, scale: Vector3) -> SharedShape {
+		match self.r#type {
+			Type::Heightmap | Type::Convex | Type::Concave => (),
+			_ => return self.shape.clone(),
+		}
+		if scale == Vector3::one() {
+			return self.shape.clone();
+		}
+		let key = [scale.x.to_bits(), scale.y.to_bits(), scale.z.to_bits()];
+		let mut cache = self.scaled_cache.lock().unwrap();
+		if let Some((_, shape)) = cache.iter().find(|(k, _)| *k == key) {
+			return shape.clone();
+		}
+		let shape = self.create_scaled(scale);
+		if cache.len() >= MAX_CACHED_SCALED_SHAPES {
+			cache.remove(0);
+		}
+		cache.push((key, shape.clone()));
+		shape
+	}
+
+	/// Creates a new scaled shape
+	fn create_scaled(&self, scale: Vector3) -> SharedShape {
 		let scale = vec_gd_to_na(scale);
 		// TODO figure out the exact way each collider is scaled in Godot for consistency
 		// The colliders where the scale is not certain are left empty for now
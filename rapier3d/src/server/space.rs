@@ -64,19 +64,23 @@ fn intersect_ray(
 					info.pick_ray(),
 				)
 				.map(|res| {
-					let (object_id, index) = match res.index {
-						BodyOrAreaIndex::Body(body) => (
-							body.map(|body| body.object_id()).expect("Invalid body"),
-							Index::Body(body),
-						),
+					let (object_id, user_flags, index) = match res.index {
+						BodyOrAreaIndex::Body(body) => {
+							let (object_id, user_flags) = body
+								.map(|body| (body.object_id(), body.user_flags()))
+								.expect("Invalid body");
+							(object_id, user_flags, Index::Body(body))
+						}
 						BodyOrAreaIndex::Area(area) => (
 							area.map(|area| area.object_id()).expect("Invalid area"),
+							0,
 							Index::Area(area),
 						),
 					};
 					result.set_position(res.position);
 					result.set_normal(res.normal);
 					result.set_object_id(object_id);
+					result.set_user_flags(user_flags);
 					result.set_index(index);
 					result.set_shape(res.shape);
 				})
@@ -209,6 +213,67 @@ mod call {
 	use ffi::{PhysicsCallError, VariantType};
 	use gdnative::prelude::*;
 
+	/// Return the maximum amount of substeps done for CCD.
+	pub fn get_max_ccd_substeps(args: &[&Variant]) -> call::Result {
+		call_check_arg_count!(args in 1..1)?;
+		let space = call_get_arg!(args[0] => Rid)?;
+		if let Ok(space) = super::get_index(space) {
+			Ok(map_or_err!(space, map_space, |space, _| {
+				i64::from(space.max_ccd_substeps()).to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set the maximum amount of substeps done for CCD.
+	pub fn set_max_ccd_substeps(args: &[&Variant]) -> call::Result {
+		call_check_arg_count!(args in 2..2)?;
+		let space = call_get_arg!(args[0] => Rid)?;
+		let substeps = call_get_arg!(args[1] => u32)?;
+		if let Ok(space) = super::get_index(space) {
+			map_or_err!(space, map_space_mut, |space, _| {
+				space.set_max_ccd_substeps(substeps);
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
+	/// Return the distance at which contacts are predicted.
+	pub fn get_ccd_prediction_distance(args: &[&Variant]) -> call::Result {
+		call_check_arg_count!(args in 1..1)?;
+		let space = call_get_arg!(args[0] => Rid)?;
+		if let Ok(space) = super::get_index(space) {
+			Ok(map_or_err!(space, map_space, |space, _| {
+				space.prediction_distance().to_variant()
+			})
+			.unwrap_or(Variant::new()))
+		} else {
+			godot_error!("Invalid index");
+			Ok(Variant::new())
+		}
+	}
+
+	/// Set the distance at which contacts are predicted, which prevents moderately fast bodies
+	/// from tunneling without enabling CCD. It applies to all bodies in the space.
+	pub fn set_ccd_prediction_distance(args: &[&Variant]) -> call::Result {
+		call_check_arg_count!(args in 2..2)?;
+		let space = call_get_arg!(args[0] => Rid)?;
+		let distance = call_get_arg!(args[1] => f32)?;
+		if let Ok(space) = super::get_index(space) {
+			map_or_err!(space, map_space_mut, |space, _| {
+				space.set_prediction_distance(distance);
+			});
+		} else {
+			godot_error!("Invalid index");
+		}
+		Ok(Variant::new())
+	}
+
 	/// Return *all* colliders that intersect with a ray. A `VariantArray` will be returned with a
 	/// number of `Dictionary` entries. Each entry has the following fields:
 	///
@@ -220,6 +285,8 @@ mod call {
 	///
 	/// * `object_id`: The `ObjectID` of the parent, which is a Body or an `Area´.
 	///
+	/// * `user_flags`: The user flags of the parent if it is a body, `0` otherwise.
+	///
 	/// * `shape`: The index of the shape in the body/area.
 	///
 	/// * `time_of_impact`: The time of impact, where
@@ -273,6 +340,7 @@ mod call {
 				let normal_key = "normal".to_variant();
 				let rid_key = "rid".to_variant();
 				let object_id_key = "object_id".to_variant();
+				let user_flags_key = "user_flags".to_variant();
 				let shape_key = "shape".to_variant();
 				let toi_key = "time_of_impact".to_variant();
 
@@ -290,19 +358,20 @@ mod call {
 						let pos = (to - from).normalize() * ri.toi + from;
 						dict.insert(position_key.clone(), pos);
 						dict.insert(normal_key.clone(), vec_na_to_gd(ri.normal));
-						let (object_id, index) = match index {
-							BodyOrAreaIndex::Body(body) => (
-								bodies
+						let (object_id, user_flags, index) = match index {
+							BodyOrAreaIndex::Body(body) => {
+								let (object_id, user_flags) = bodies
 									.get(body.into())
-									.map(|b| b.object_id())
-									.expect("Invalid body"),
-								Index::Body(body),
-							),
+									.map(|b| (b.object_id(), b.user_flags()))
+									.expect("Invalid body");
+								(object_id, user_flags, Index::Body(body))
+							}
 							BodyOrAreaIndex::Area(area) => (
 								areas
 									.get(area.into())
 									.map(|a| a.object_id())
 									.expect("Invalid area"),
+								0,
 								Index::Area(area),
 							),
 						};
@@ -311,6 +380,7 @@ mod call {
 							object_id_key.clone(),
 							object_id.map(ObjectID::get).unwrap_or(0),
 						);
+						dict.insert(user_flags_key.clone(), user_flags);
 						dict.insert(shape_key.clone(), shape_index);
 						dict.insert(rid_key.clone(), rid);
 						dict.insert(toi_key.clone(), ri.toi);
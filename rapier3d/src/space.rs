@@ -1,8 +1,11 @@
-use crate::area::Area;
+use crate::area::{Area, MonitorEvent};
+use crate::body::Body;
+use crate::indices::Indices;
 use crate::server::{AreaIndex, BodyIndex, MapIndex, ShapeIndex, SpaceIndex};
 use crate::util::*;
 use crate::{area, body};
 use core::convert::TryFrom;
+use core::mem;
 use gdnative::prelude::*;
 use rapier3d::crossbeam::channel::{self, Receiver, Sender};
 use rapier3d::dynamics::{
@@ -13,7 +16,9 @@ use rapier3d::geometry::{
 	BroadPhase, Collider, ColliderHandle, ColliderSet, ContactEvent, InteractionGroups,
 	IntersectionEvent, NarrowPhase, Ray, SolverFlags,
 };
-use rapier3d::na::Point3;
+use rapier3d::na::{self, Point3};
+use rapier3d::parry::bounding_volume::BoundingVolume;
+use rapier3d::parry::query::{self, NonlinearRigidMotion, TOIStatus};
 use rapier3d::pipeline::{
 	ContactModificationContext, EventHandler, PairFilterContext, PhysicsHooks, PhysicsPipeline,
 	QueryPipeline,
@@ -45,9 +50,10 @@ pub struct Space {
 	body_exclusions: BodyExclusionHooks,
 	event_handler: IntersectionEventCollector,
 	intersection_recv: Receiver<IntersectionEvent>,
-	contact_recv: Receiver<(BodyIndex, body::ContactEvent)>,
 
 	area_map: BTreeMap<i32, Vec<RigidBodyHandle>>,
+	area_entry_count: u64,
+	body_rescans: Vec<RigidBodyHandle>,
 
 	debug_contacts: Vec<Vector3>,
 	debug_contact_count: usize,
@@ -77,7 +83,6 @@ struct BodyExclusionHooks {
 	// * SparseVec is likely densely packed -> not many "holes" in the Vec.
 	// * Amount of body exclusions is likely small -> Vec is compact and maybe faster.
 	exclusions: Vec<Vec<BodyIndex>>,
-	contacts_sender: Sender<(BodyIndex, body::ContactEvent)>,
 }
 
 struct IntersectionEventCollector {
@@ -87,14 +92,14 @@ struct IntersectionEventCollector {
 impl Space {
 	pub fn new() -> Self {
 		let (intersection_send, intersection_recv) = channel::unbounded();
-		let (contact_send, contact_recv) = channel::unbounded();
 		let query_pipeline = QueryPipeline::new();
 		let narrow_phase = NarrowPhase::new();
+		let integration_parameters = IntegrationParameters::default();
 		Space {
 			physics_pipeline: PhysicsPipeline::new(),
 			query_pipeline,
 			gravity: Vector3::new(0.0, -9.81, 0.0),
-			integration_parameters: IntegrationParameters::default(),
+			integration_parameters,
 			broad_phase: BroadPhase::new(),
 			narrow_phase,
 
@@ -104,17 +109,18 @@ impl Space {
 			joints: JointSet::new(),
 
 			ccd_solver: CCDSolver::new(),
-			body_exclusions: BodyExclusionHooks::new(contact_send),
+			body_exclusions: BodyExclusionHooks::new(),
 			event_handler: IntersectionEventCollector {
 				sender: intersection_send,
 			},
 			intersection_recv,
-			contact_recv,
 			query_pipeline_out_of_date: false,
 			index: None,
 			enabled: true,
 
 			area_map: BTreeMap::new(),
+			area_entry_count: 0,
+			body_rescans: Vec::new(),
 
 			debug_contacts: Vec::new(),
 			debug_contact_count: 0,
@@ -139,6 +145,7 @@ impl Space {
 
 		// Step
 		self.integration_parameters.dt = delta;
+		self.sweep_kinematic_bodies(delta);
 		self.physics_pipeline.step(
 			&vec_gd_to_na(self.gravity),
 			&self.integration_parameters,
@@ -154,16 +161,12 @@ impl Space {
 		);
 		self.query_pipeline_out_of_date = true;
 
-		// Clear area events
+		// Find stale areas
 		let mut remove = Vec::new();
 		for (&prio, vec) in self.area_map.iter() {
 			let mut rm = Vec::new();
 			for (i, &area) in vec.iter().enumerate() {
-				if let Some(area) = self.bodies.get(area) {
-					let area = Area::get_rigidbody_userdata(area).expect("Invalid area userdata");
-					let area = areas.get_mut(area.into()).expect("Invalid area index");
-					area.clear_events();
-				} else {
+				if self.bodies.get(area).is_none() {
 					// u32 is slightly more efficient and cannot be exceeded anyways, as AreaIndex
 					// also uses u32.
 					rm.push(i as u32);
@@ -185,56 +188,126 @@ impl Space {
 			}
 		}
 
-		// Process area intersections
+		// Process area intersections. Each event gets a new entry so the space overrides of areas
+		// with equal priorities can be ordered by when the body entered them.
 		while let Ok(event) = self.intersection_recv.try_recv() {
-			let a = &self.colliders[event.collider1];
-			let b = &self.colliders[event.collider2];
-			let (area, body) = if let Some(a) = Area::get_collider_userdata(a) {
-				if let Some(b) = Area::get_collider_userdata(b) {
-					let (a, b) = (a.index(), b.index());
-					let (area_a, area_b) = areas.get2_mut(a.into(), b.into());
-					let area_a = area_a.expect("Invalid area A index");
-					let area_b = area_b.expect("Invalid area B index");
-					if area_b.monitorable() {
-						area_a.push_area_event(b, event.intersecting);
+			self.area_entry_count += 1;
+			self.process_intersection(
+				&mut areas,
+				&bodies,
+				event.collider1,
+				event.collider2,
+				event.intersecting,
+				self.area_entry_count,
+			);
+		}
+		self.area_entry_count += 1;
+		let body_rescans = mem::take(&mut self.body_rescans);
+		self.rescan_intersections(&mut areas, &bodies, &body_rescans, self.area_entry_count);
+
+		// Process body contacts
+		// This is done after the step as the impulses aren't known before
+		for pair in self.narrow_phase.contact_pairs() {
+			if !pair.has_any_active_contact {
+				continue;
+			}
+			let c1 = &self.colliders[pair.collider1];
+			let c2 = &self.colliders[pair.collider2];
+			let (a, b) = match (
+				body::ColliderUserdata::try_from(c1),
+				body::ColliderUserdata::try_from(c2),
+			) {
+				(Ok(a), Ok(b)) => (a, b),
+				_ => continue,
+			};
+			if !a.monitoring() && !b.monitoring() {
+				continue;
+			}
+			for manifold in pair.manifolds.iter() {
+				let normal = vec_na_to_gd(manifold.data.normal);
+				let rb1 = manifold.data.rigid_body1.map(|h| &self.bodies[h]);
+				let rb2 = manifold.data.rigid_body2.map(|h| &self.bodies[h]);
+				for point in manifold.points.iter() {
+					// Speculative contacts aren't touching yet, so don't report them
+					if point.dist > 0.0 {
+						continue;
 					}
-					if area_a.monitorable() {
-						area_b.push_area_event(a, event.intersecting);
+					let position = c1.position() * point.local_p1;
+					let depth = -point.dist;
+					let impulse = point.data.impulse;
+					let velocity1 =
+						rb1.map_or(Vector3::zero(), |rb| velocity_at_point(rb, &position));
+					let velocity2 =
+						rb2.map_or(Vector3::zero(), |rb| velocity_at_point(rb, &position));
+					if a.monitoring() {
+						let contact = body::ContactEvent::new(
+							vec_na_to_gd(position.coords),
+							b.index(),
+							b.shape(),
+							a.shape(),
+							normal,
+							impulse,
+							depth,
+							velocity2,
+							velocity1,
+							user_flags(rb2),
+						);
+						bodies
+							.get_mut(a.index().into())
+							.expect("Invalid body index")
+							.add_contact(contact);
+					}
+					if b.monitoring() {
+						let contact = body::ContactEvent::new(
+							vec_na_to_gd(position.coords),
+							a.index(),
+							a.shape(),
+							b.shape(),
+							normal,
+							impulse,
+							depth,
+							velocity1,
+							velocity2,
+							user_flags(rb1),
+						);
+						bodies
+							.get_mut(b.index().into())
+							.expect("Invalid body index")
+							.add_contact(contact);
 					}
-					continue;
-				} else {
-					(a, body::ColliderUserdata::try_from(b).unwrap().index())
 				}
-			} else if let Some(b) = Area::get_collider_userdata(b) {
-				(b, body::ColliderUserdata::try_from(a).unwrap().index())
-			} else {
-				panic!("Neither collider is an area");
-			};
-			let area = areas.get_mut(area.into()).expect("Invalid area index");
-			area.push_body_event(body, event.intersecting);
+			}
 		}
-
-		// Process body contacts
-		while let Ok((body, event)) = self.contact_recv.try_recv() {
-			bodies
-				.get_mut(body.into())
-				.expect("Invalid body index")
-				.add_contact(event);
+		for rb in self.bodies.iter().map(|v| v.1) {
+			if let Ok(body) = body::RigidBodyUserdata::try_from(rb) {
+				if body.monitoring() {
+					bodies
+						.get_mut(body.into())
+						.expect("Invalid body index")
+						.finish_contacts();
+				}
+			}
 		}
 
-		// Register area space overrides to bodies
-		for &area in self.area_map.values().rev().flat_map(|v| v.iter()) {
+		// Register area space overrides to bodies. The bodies sort them by priority and entry order
+		// when the overrides are applied.
+		for &area in self.area_map.values().flatten() {
 			let rb = &self.bodies[area];
 			let area = Area::get_rigidbody_userdata(rb).expect("Area body has invalid userdata");
 			let area = areas.get_mut(area.into()).expect("Invalid area index");
-			area.apply_events(&rb, &mut bodies, &self.bodies);
+			area.apply_events(&rb, &mut bodies, &self.bodies, &self.colliders);
 		}
 
 		// Apply space overrides to bodies
 		for rb in self.bodies.iter_mut().map(|v| v.1) {
 			if let Ok(body) = body::RigidBodyUserdata::try_from(&*rb) {
 				let body = bodies.get_mut(body.into()).expect("Invalid body index");
-				body.apply_area_overrides(rb, self.default_linear_damp, self.default_angular_damp);
+				body.apply_area_overrides(
+					rb,
+					self.gravity,
+					self.default_linear_damp,
+					self.default_angular_damp,
+				);
 			}
 		}
 
@@ -242,6 +315,110 @@ impl Space {
 		self.debug_contacts.clear();
 	}
 
+	/// Passes an intersection event to the area or areas involved.
+	///
+	/// Intersections that don't pass the layer and mask filter are passed as exit events. The
+	/// areas ignore exit events of bodies and areas they didn't detect, so this also makes a
+	/// rescan forget anything that isn't detected anymore.
+	fn process_intersection(
+		&self,
+		areas: &mut Indices<Area>,
+		bodies: &Indices<Body>,
+		collider1: ColliderHandle,
+		collider2: ColliderHandle,
+		intersecting: bool,
+		entry: u64,
+	) {
+		let a = &self.colliders[collider1];
+		let b = &self.colliders[collider2];
+		let (area, body, body_groups) = if let Some(a_ud) = Area::get_collider_userdata(a) {
+			if let Some(b_ud) = Area::get_collider_userdata(b) {
+				let (a_shape, b_shape) = (a_ud.shape(), b_ud.shape());
+				let (a, b) = (a_ud.index(), b_ud.index());
+				let (area_a, area_b) = areas.get2_mut(a.into(), b.into());
+				let area_a = area_a.expect("Invalid area A index");
+				let area_b = area_b.expect("Invalid area B index");
+				let detected =
+					intersecting && area_b.monitorable() && area_a.detects_layer(area_b.layer());
+				let id = area_b.object_id();
+				let e = MonitorEvent::new(b, id, b_shape, a_shape, detected);
+				area_a.push_area_event(e);
+				let detected =
+					intersecting && area_a.monitorable() && area_b.detects_layer(area_a.layer());
+				let id = area_a.object_id();
+				let e = MonitorEvent::new(a, id, a_shape, b_shape, detected);
+				area_b.push_area_event(e);
+				return;
+			} else {
+				let body = body::ColliderUserdata::try_from(b).unwrap();
+				(a_ud, body, b.collision_groups())
+			}
+		} else if let Some(b_ud) = Area::get_collider_userdata(b) {
+			let body = body::ColliderUserdata::try_from(a).unwrap();
+			(b_ud, body, a.collision_groups())
+		} else {
+			panic!("Neither collider is an area");
+		};
+		let (area_index, area_shape) = (area.index(), area.shape());
+		let area = areas
+			.get_mut(area_index.into())
+			.expect("Invalid area index");
+		let detected = intersecting && area.detects_layer(body_groups.memberships);
+		let id = if area.is_monitoring_bodies() {
+			bodies
+				.get(body.index().into())
+				.expect("Invalid body index")
+				.object_id()
+		} else {
+			None
+		};
+		let event = MonitorEvent::new(body.index(), id, body.shape(), area_shape, detected);
+		area.push_body_event(event, entry);
+	}
+
+	/// Passes the current intersections of areas whose layer, mask or monitorability changed and
+	/// of bodies whose layer changed, as those affect what is detected without any new
+	/// intersection events
+	fn rescan_intersections(
+		&self,
+		areas: &mut Indices<Area>,
+		bodies: &Indices<Body>,
+		body_rescans: &[RigidBodyHandle],
+		entry: u64,
+	) {
+		let rescan = |rb: &RigidBody, areas: &mut Indices<Area>| {
+			for &collider in rb.colliders() {
+				for (c1, c2, intersecting) in self.narrow_phase.intersections_with(collider) {
+					if intersecting {
+						self.process_intersection(areas, bodies, c1, c2, true, entry);
+					}
+				}
+			}
+		};
+		for &area in self.area_map.values().flatten() {
+			let rb = &self.bodies[area];
+			let index = Area::get_rigidbody_userdata(rb).expect("Area body has invalid userdata");
+			let area = areas.get_mut(index.into()).expect("Invalid area index");
+			if area.take_rescan_intersections() {
+				rescan(rb, areas);
+			}
+		}
+		for &rb in body_rescans {
+			// The body may have been removed since
+			if let Some(rb) = self.bodies.get(rb) {
+				rescan(rb, areas);
+			}
+		}
+	}
+
+	/// Makes the areas around the given body check whether they still detect it during the
+	/// next step, which is necessary after its layer changed.
+	pub fn rescan_body_intersections(&mut self, body: RigidBodyHandle) {
+		if !self.body_rescans.contains(&body) {
+			self.body_rescans.push(body);
+		}
+	}
+
 	/// Gets the index of this space
 	///
 	/// # Panics
@@ -317,6 +494,148 @@ impl Space {
 		self.body_exclusions.remove_exclusion(index_a, index_b)
 	}
 
+	/// Rapier's CCD only prevents fast dynamic bodies from passing through other bodies, so a fast
+	/// kinematic body would still pass through any dynamic bodies in its way. This sweeps the
+	/// colliders of kinematic bodies with CCD enabled to their next position and gives each
+	/// dynamic body they hit an impulse at the point of impact, like a contact would have if the
+	/// bodies touched during the step.
+	fn sweep_kinematic_bodies(&mut self, delta: f32) {
+		let kinematic = self
+			.bodies
+			.iter()
+			.filter(|(_, rb)| rb.is_kinematic() && rb.is_ccd_enabled())
+			.map(|(handle, _)| handle)
+			.collect::<Vec<_>>();
+		if kinematic.is_empty() {
+			return;
+		}
+		self.update_query_pipeline();
+
+		// The earliest hit of each dynamic body, along with the normal, the point and the velocity
+		// of the kinematic body at that point. Bodies that already touch a kinematic body are
+		// handled by the regular contacts, so those are marked with a `None`.
+		type Hit = (f32, na::Vector3<f32>, Point3<f32>, na::Vector3<f32>);
+		let mut hits: Vec<(RigidBodyHandle, Option<Hit>)> = Vec::new();
+		for handle in kinematic {
+			let rb = &self.bodies[handle];
+			let start = *rb.position();
+			let end = if rb.body_type() == RigidBodyType::KinematicPositionBased {
+				*rb.next_position()
+			} else {
+				rb.predict_position_using_velocity_and_forces(delta)
+			};
+			let linvel = (end.translation.vector - start.translation.vector) / delta;
+			let angvel = (end.rotation * start.rotation.inverse()).scaled_axis() / delta;
+			if linvel == na::Vector3::zeros() && angvel == na::Vector3::zeros() {
+				continue;
+			}
+			let origin = Point3::from(start.translation.vector);
+			for &collider in rb.colliders() {
+				let co1 = &self.colliders[collider];
+				if co1.is_sensor() {
+					continue;
+				}
+				let end_position = end * start.inverse() * co1.position();
+				let aabb = co1
+					.compute_aabb()
+					.merged(&co1.shape().compute_aabb(&end_position));
+				let motion1 = NonlinearRigidMotion::new(
+					*co1.position(),
+					co1.position().inverse_transform_point(&origin),
+					linvel,
+					angvel,
+				);
+				let mut callback = |&other: &ColliderHandle| {
+					let co2 = &self.colliders[other];
+					let body2 = if let Some(body2) = co2.parent() {
+						body2
+					} else {
+						return true;
+					};
+					let rb2 = &self.bodies[body2];
+					if !rb2.is_dynamic()
+						|| co2.is_sensor() || !co1.solver_groups().test(co2.solver_groups())
+						|| self.body_exclusions.is_excluded(rb, rb2)
+					{
+						return true;
+					}
+					let index = if let Some(i) = hits.iter().position(|h| h.0 == body2) {
+						i
+					} else {
+						hits.push((
+							body2,
+							Some((f32::INFINITY, na::zero(), Point3::origin(), na::zero())),
+						));
+						hits.len() - 1
+					};
+					let touching = self
+						.narrow_phase
+						.contact_pair(collider, other)
+						.map_or(false, |pair| pair.has_any_active_contact);
+					if touching {
+						hits[index].1 = None;
+						return true;
+					}
+					let com2 = rb2.position() * rb2.mass_properties().local_com;
+					let motion2 = NonlinearRigidMotion::new(
+						*co2.position(),
+						co2.position().inverse_transform_point(&com2),
+						*rb2.linvel(),
+						*rb2.angvel(),
+					);
+					let toi = query::nonlinear_time_of_impact(
+						&motion1,
+						co1.shape(),
+						&motion2,
+						co2.shape(),
+						0.0,
+						delta,
+						true,
+					);
+					if let (Ok(Some(toi)), Some(hit)) = (toi, &mut hits[index].1) {
+						if toi.status != TOIStatus::Penetrating && toi.toi < hit.0 {
+							let position1 = motion1.position_at_time(toi.toi);
+							let point = position1 * toi.witness1;
+							let normal = position1.rotation * toi.normal1.into_inner();
+							let origin1 = origin + linvel * toi.toi;
+							let velocity1 = linvel + angvel.cross(&(point - origin1));
+							*hit = (toi.toi, normal, point, velocity1);
+						}
+					}
+					true
+				};
+				self.query_pipeline
+					.colliders_with_aabb_intersecting_aabb(&aabb, &mut callback);
+			}
+		}
+
+		// Apply a single impulse per body that makes it move away from the kinematic body at the
+		// point of impact, using the effective mass along the normal.
+		for (handle, hit) in hits {
+			let (toi, normal, point, velocity1) = match hit {
+				Some(hit) if hit.0.is_finite() => hit,
+				_ => continue,
+			};
+			let rb = &mut self.bodies[handle];
+			let mp = rb.mass_properties();
+			let com = rb.position() * mp.local_com + rb.linvel() * toi;
+			let arm = point - com;
+			let velocity2 = rb.linvel() + rb.angvel().cross(&arm);
+			let approach = (velocity1 - velocity2).dot(&normal);
+			if approach <= 0.0 {
+				continue;
+			}
+			let rotation = rb.position().rotation.to_rotation_matrix().into_inner();
+			let inv_inertia =
+				rotation * mp.reconstruct_inverse_inertia_matrix() * rotation.transpose();
+			let torque_arm = arm.cross(&normal);
+			let inv_mass = mp.inv_mass + torque_arm.dot(&(inv_inertia * torque_arm));
+			if inv_mass > 0.0 {
+				rb.apply_impulse_at_point(normal * (approach / inv_mass), point, true);
+			}
+		}
+	}
+
 	/// Ensure the pipeline is up to date.
 	pub fn update_query_pipeline(&mut self) {
 		if self.query_pipeline_out_of_date {
@@ -526,6 +845,39 @@ impl Space {
 		self.gravity = gravity;
 	}
 
+	/// Returns the maximum amount of substeps done for CCD
+	pub fn max_ccd_substeps(&self) -> u32 {
+		self.integration_parameters.max_ccd_substeps as u32
+	}
+
+	/// Sets the maximum amount of substeps done for CCD. Higher values prevent more fast bodies
+	/// from tunneling but are more expensive.
+	pub fn set_max_ccd_substeps(&mut self, substeps: u32) {
+		self.integration_parameters.max_ccd_substeps = substeps as usize;
+	}
+
+	/// Returns the distance at which contacts are predicted before the colliders touch
+	pub fn prediction_distance(&self) -> f32 {
+		self.integration_parameters.prediction_distance
+	}
+
+	/// Sets the distance at which contacts are predicted before the colliders touch, which
+	/// prevents tunneling of moderately fast bodies without the cost of full CCD. Rapier only
+	/// supports a single prediction distance for all bodies in a space.
+	pub fn set_prediction_distance(&mut self, distance: f32) {
+		self.integration_parameters.prediction_distance = distance;
+	}
+
+	/// Returns the default linear damp of rigid bodies in this space
+	pub fn default_linear_damp(&self) -> f32 {
+		self.default_linear_damp
+	}
+
+	/// Returns the default angular damp of rigid bodies in this space
+	pub fn default_angular_damp(&self) -> f32 {
+		self.default_angular_damp
+	}
+
 	/// Sets the default linear damp of rigid bodies in this space
 	pub fn set_default_linear_damp(&mut self, damp: f32) {
 		self.default_linear_damp = damp;
@@ -549,6 +901,7 @@ impl Space {
 			}
 		}
 
+		rb.user_data &= !(0xffff_ffff_u128 << 96);
 		rb.user_data |= (priority as u32 as u128) << 96;
 		self.area_map
 			.entry(priority)
@@ -556,6 +909,20 @@ impl Space {
 			.push(area);
 	}
 
+	/// Removes an area and its colliders from this space
+	pub fn remove_area(&mut self, area: RigidBodyHandle) -> Option<RigidBody> {
+		let prio = (self.bodies.get(area)?.user_data >> 96) as i32;
+		if let Some(v) = self.area_map.get_mut(&prio) {
+			if let Some(i) = v.iter().position(|e| *e == area) {
+				v.remove(i);
+			}
+			if v.is_empty() {
+				self.area_map.remove(&prio);
+			}
+		}
+		self.remove_body(area)
+	}
+
 	/// Returns a reference to a body if it exists
 	pub fn get_body(&self, body: RigidBodyHandle) -> Option<&RigidBody> {
 		self.bodies.get(body)
@@ -656,19 +1023,39 @@ pub struct ExclusionAlreadyExists;
 pub struct ExclusionDoesntExist;
 
 impl BodyExclusionHooks {
-	fn new(contacts_sender: Sender<(BodyIndex, body::ContactEvent)>) -> Self {
+	fn new() -> Self {
 		Self {
 			exclusions: Vec::<Vec<BodyIndex>>::new(),
-			contacts_sender,
 		}
 	}
 
+	/// Returns whether the two bodies exclude each other
+	fn is_excluded(&self, rb1: &RigidBody, rb2: &RigidBody) -> bool {
+		if let Ok(a) = body::RigidBodyUserdata::try_from(rb1) {
+			if let Ok(b) = body::RigidBodyUserdata::try_from(rb2) {
+				let (a, b) = (a.index(), b.index());
+				let (a, b) = if a.index() < b.index() {
+					(a, b)
+				} else {
+					(b, a)
+				};
+				if let Some(indices) = self.exclusions.get(a.index() as usize) {
+					return indices.contains(&b);
+				}
+			}
+		}
+		false
+	}
+
 	fn add_exclusion(
 		&mut self,
 		index_a: BodyIndex,
 		index_b: BodyIndex,
 	) -> Result<(), ExclusionAlreadyExists> {
-		// TODO how should we handle self-exclusions? (index_a == index_b)
+		if index_a == index_b {
+			// Colliders attached to the same body never interact, so there is nothing to exclude
+			return Ok(());
+		}
 		let (a, b) = (index_a, index_b);
 		let (a, b) = if a.index() < b.index() {
 			(a, b)
@@ -690,13 +1077,15 @@ impl BodyExclusionHooks {
 		}
 	}
 
-	// TODO implement body_remove_exception and remove the allow
-	#[allow(dead_code)]
 	fn remove_exclusion(
 		&mut self,
 		index_a: BodyIndex,
 		index_b: BodyIndex,
 	) -> Result<(), ExclusionDoesntExist> {
+		if index_a == index_b {
+			// Ditto
+			return Ok(());
+		}
 		let (a, b) = (index_a, index_b);
 		let (a, b) = if a.index() < b.index() {
 			(a, b)
@@ -723,25 +1112,19 @@ impl PhysicsHooks<RigidBodySet, ColliderSet> for BodyExclusionHooks {
 	) -> Option<SolverFlags> {
 		let mut monitor = false;
 		let rb1 = &context.bodies[context.rigid_body1.unwrap()];
+		let rb2 = &context.bodies[context.rigid_body2.unwrap()];
 		if let Ok(a) = body::RigidBodyUserdata::try_from(rb1) {
-			let rb2 = &context.bodies[context.rigid_body2.unwrap()];
 			if let Ok(b) = body::RigidBodyUserdata::try_from(rb2) {
 				monitor = a.monitoring() || b.monitoring();
-				let (a, b) = (a.index(), b.index());
-				let (a, b) = if a.index() < b.index() {
-					(a, b)
-				} else {
-					(b, a)
-				};
-				if let Some(indices) = self.exclusions.get(a.index() as usize) {
-					for &i in indices.iter() {
-						if i == b {
-							return None;
-						}
-					}
-				}
 			}
 		}
+		// The actual layers and masks of bodies are stored in the solver groups, see
+		// `Body::collider_collision_groups`
+		let c1 = &context.colliders[context.collider1];
+		let c2 = &context.colliders[context.collider2];
+		if !c1.solver_groups().test(c2.solver_groups()) || self.is_excluded(rb1, rb2) {
+			return None;
+		}
 		Some(if monitor {
 			SolverFlags::all()
 		} else {
@@ -749,45 +1132,76 @@ impl PhysicsHooks<RigidBodySet, ColliderSet> for BodyExclusionHooks {
 		})
 	}
 
-	fn filter_intersection_pair(&self, _: &PairFilterContext<RigidBodySet, ColliderSet>) -> bool {
-		false
+	fn filter_intersection_pair(
+		&self,
+		context: &PairFilterContext<RigidBodySet, ColliderSet>,
+	) -> bool {
+		// Only areas use this hook. Rapier doesn't report intersections between non-dynamic
+		// bodies by default, but areas should also detect static and kinematic bodies and other
+		// areas. Layers and masks are checked when the events are processed, as that depends on
+		// which side is monitoring.
+		let c1 = &context.colliders[context.collider1];
+		let c2 = &context.colliders[context.collider2];
+		Area::get_collider_userdata(c1).is_some() || Area::get_collider_userdata(c2).is_some()
 	}
 
 	fn modify_solver_contacts(
 		&self,
 		context: &mut ContactModificationContext<RigidBodySet, ColliderSet>,
 	) {
+		// Combine the materials the same way Godot does. Rough shapes have a negative friction and
+		// absorbent shapes a negative restitution.
 		let c1 = &context.colliders[context.collider1];
-		if let Ok(a) = body::ColliderUserdata::try_from(c1) {
-			let c2 = &context.colliders[context.collider2];
-			if let Ok(b) = body::ColliderUserdata::try_from(c2) {
-				for c in context.solver_contacts.iter() {
-					if a.monitoring() {
-						let contact = body::ContactEvent::new(
-							vec_na_to_gd(c.point.coords),
-							b.index(),
-							b.shape(),
-							a.shape(),
-							vec_na_to_gd(*context.normal),
-						);
-						let _ = self.contacts_sender.try_send((a.index(), contact));
-					}
-					if b.monitoring() {
-						let contact = body::ContactEvent::new(
-							vec_na_to_gd(c.point.coords),
-							a.index(),
-							a.shape(),
-							b.shape(),
-							vec_na_to_gd(*context.normal),
-						);
-						let _ = self.contacts_sender.try_send((b.index(), contact));
+		let c2 = &context.colliders[context.collider2];
+		let friction = c1.friction().min(c2.friction()).abs();
+		let restitution = (c1.restitution() + c2.restitution()).clamp(0.0, 1.0);
+		for c in context.solver_contacts.iter_mut() {
+			c.friction = friction;
+			c.restitution = restitution;
+		}
+
+		// Bodies with some but not all translation axes locked must not be pushed along those
+		// axes, so the locked components are removed from the normal. Contacts that can only push
+		// along locked axes are dropped.
+		let mut locks = [false; 3];
+		for rb in [context.rigid_body1, context.rigid_body2].iter().flatten() {
+			let rb = &context.bodies[*rb];
+			if let Ok(ud) = body::RigidBodyUserdata::try_from(rb) {
+				if rb.is_dynamic() {
+					for (lock, &l) in locks.iter_mut().zip(ud.partial_translation_locks().iter()) {
+						*lock |= l;
 					}
 				}
 			}
 		}
+		if locks.iter().any(|&l| l) {
+			let mut normal = *context.normal;
+			for (n, &l) in normal.iter_mut().zip(locks.iter()) {
+				if l {
+					*n = 0.0;
+				}
+			}
+			if let Some(normal) = normal.try_normalize(1e-4) {
+				*context.normal = normal;
+			} else {
+				context.solver_contacts.clear();
+			}
+		}
 	}
 }
 
+/// Returns the user flags of a body, or `0` if it isn't a body
+fn user_flags(body: Option<&RigidBody>) -> u32 {
+	body.and_then(|rb| body::RigidBodyUserdata::try_from(rb).ok())
+		.map_or(0, |ud| ud.user_flags())
+}
+
+/// Returns the velocity of a body at the given point in global space
+fn velocity_at_point(body: &RigidBody, point: &Point3<f32>) -> Vector3 {
+	let com = body.position() * body.mass_properties().local_com;
+	vec_na_to_gd(body.linvel() + body.angvel().cross(&(point - com)))
+}
+
 impl EventHandler for IntersectionEventCollector {
 	fn handle_intersection_event(&self, event: IntersectionEvent) {
 		let _ = self.sender.try_send(event);
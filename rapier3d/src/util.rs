@@ -35,6 +35,19 @@ pub fn isometry_to_transform(isometry: &Isometry<f32>) -> Transform {
 	Transform { basis, origin }
 }
 
+pub fn isometry_and_scale_to_transform(isometry: &Isometry<f32>, scale: Vector3) -> Transform {
+	let mut transform = isometry_to_transform(isometry);
+	for row in transform.basis.elements.iter_mut() {
+		*row = row.component_mul(scale);
+	}
+	transform
+}
+
+pub fn basis_to_mat3(basis: &Basis) -> na::Matrix3<f32> {
+	let [x, y, z] = basis.elements;
+	na::Matrix3::new(x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z)
+}
+
 pub fn mat3_to_basis(mat: &na::Matrix3<f32>) -> Basis {
 	Basis {
 		elements: [
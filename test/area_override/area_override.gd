@@ -0,0 +1,153 @@
+extends Node
+
+# Run with `godot --no-window res://test/area_override/area_override.tscn`. The exit code is the
+# amount of cases where the gravity or damping of a body doesn't match Godot's Bullet backend.
+#
+# Each case puts bodies inside a high priority area and a low priority area and prints whether the
+# gravity and damping match Godot's Bullet backend. The space, the high priority area and the low
+# priority area have a gravity and damp of 1, 2 and 4 respectively, so every combination has a
+# unique result.
+#
+# The tie case has two areas with equal priorities. The bodies start inside the area that was added
+# last and enter the other area later, which should then go first.
+
+
+const MODES := [
+	PhysicsServer.AREA_SPACE_OVERRIDE_DISABLED,
+	PhysicsServer.AREA_SPACE_OVERRIDE_COMBINE,
+	PhysicsServer.AREA_SPACE_OVERRIDE_COMBINE_REPLACE,
+	PhysicsServer.AREA_SPACE_OVERRIDE_REPLACE,
+	PhysicsServer.AREA_SPACE_OVERRIDE_REPLACE_COMBINE,
+]
+const MODE_NAMES := ["Disabled", "Combine", "CombineReplace", "Replace", "ReplaceCombine"]
+
+# Rows are the mode of the high priority area, columns the mode of the low priority area
+const EXPECTED := [
+	[1, 5, 4, 4, 5],
+	[3, 7, 6, 4, 5],
+	[2, 2, 2, 2, 2],
+	[2, 2, 2, 2, 2],
+	[3, 7, 6, 4, 5],
+]
+
+export var enter_frame := 5
+export var start_frame := 10
+export var end_frame := 30
+
+var cases := []
+var late_bodies := []
+var frame := 0
+
+
+class Probe:
+	extends RigidBody
+
+	var linear_velocity_seen := Vector3()
+	var linear_damp_seen := 0.0
+	var angular_damp_seen := 0.0
+
+	func _integrate_forces(state):
+		linear_velocity_seen = state.linear_velocity
+		linear_damp_seen = state.total_linear_damp
+		angular_damp_seen = state.total_angular_damp
+
+
+func _ready():
+	var space := get_viewport().world.space
+	PhysicsServer.area_set_param(space, PhysicsServer.AREA_PARAM_GRAVITY_VECTOR, Vector3(1, 0, 0))
+	PhysicsServer.area_set_param(space, PhysicsServer.AREA_PARAM_GRAVITY, 1.0)
+	PhysicsServer.area_set_param(space, PhysicsServer.AREA_PARAM_LINEAR_DAMP, 1.0)
+	PhysicsServer.area_set_param(space, PhysicsServer.AREA_PARAM_ANGULAR_DAMP, 1.0)
+
+	for high in len(MODES):
+		for low in len(MODES):
+			var position := Vector3(high * 100, 0, low * 100)
+			_area(position, MODES[high], 2.0, 2)
+			_area(position, MODES[low], 4.0, 1)
+			_case(position, "%s over %s" % [MODE_NAMES[high], MODE_NAMES[low]], EXPECTED[high][low])
+
+	var tie_position := Vector3(-100, 0, 0)
+	_area(tie_position, PhysicsServer.AREA_SPACE_OVERRIDE_REPLACE, 2.0, 1)
+	_area(tie_position + Vector3(30, 0, 0), PhysicsServer.AREA_SPACE_OVERRIDE_REPLACE, 4.0, 1)
+	var c: Dictionary = _case(tie_position + Vector3(40, 0, 0), "Replace tied with Replace", 2)
+	late_bodies = [c.gravity_body, c.damp_body]
+
+
+func _physics_process(delta):
+	frame += 1
+	if frame == enter_frame:
+		for b in late_bodies:
+			b.translation.x -= 25
+	if frame == start_frame:
+		for c in cases:
+			c.start_velocity = c.gravity_body.linear_velocity_seen
+	if frame < end_frame:
+		return
+	set_physics_process(false)
+
+	var failed := 0
+	var time := (end_frame - start_frame) * delta
+	for c in cases:
+		var gravity: Vector3 = (c.gravity_body.linear_velocity_seen - c.start_velocity) / time
+		var linear_damp: float = c.damp_body.linear_damp_seen
+		var angular_damp: float = c.damp_body.angular_damp_seen
+		if (
+			abs(gravity.x - c.expected) > 0.01
+			or not is_equal_approx(linear_damp, c.expected)
+			or not is_equal_approx(angular_damp, c.expected)
+		):
+			failed += 1
+			print(
+				"%s: expected %d, got gravity %s, linear damp %f, angular damp %f"
+				% [c.name, c.expected, gravity, linear_damp, angular_damp]
+			)
+	print("%d of %d cases failed" % [failed, len(cases)])
+	get_tree().quit(failed)
+
+
+func _case(position: Vector3, name: String, expected: int) -> Dictionary:
+	# One body ignores damping so its velocity only depends on the gravity
+	var gravity_body := _probe(position)
+	gravity_body.linear_damp = 0.0
+	gravity_body.angular_damp = 0.0
+	var damp_body := _probe(position + Vector3(0, 0, 5))
+	var c := {
+		name = name,
+		expected = expected,
+		gravity_body = gravity_body,
+		damp_body = damp_body,
+		start_velocity = Vector3(),
+	}
+	cases.push_back(c)
+	return c
+
+
+func _area(position: Vector3, mode: int, value: float, priority: int) -> void:
+	var shape := CollisionShape.new()
+	shape.shape = BoxShape.new()
+	shape.shape.extents = Vector3(20, 20, 20)
+	var a := Area.new()
+	a.translation = position
+	a.space_override = mode
+	a.gravity_vec = Vector3(1, 0, 0)
+	a.gravity = value
+	a.linear_damp = value
+	a.angular_damp = value
+	a.priority = priority
+	a.add_child(shape)
+	add_child(a)
+	# Changing the priority back and forth must not leave the area behind at an old priority
+	a.priority = priority + 1
+	a.priority = priority
+
+
+func _probe(position: Vector3) -> Probe:
+	var shape := CollisionShape.new()
+	shape.shape = SphereShape.new()
+	shape.shape.radius = 0.5
+	var b := Probe.new()
+	b.translation = position
+	b.can_sleep = false
+	b.add_child(shape)
+	add_child(b)
+	return b
@@ -0,0 +1,91 @@
+extends Node
+
+# Run with `godot --no-window res://test/ccd/ccd.tscn`. The exit code is the amount of balls that
+# tunneled through the plate or the blade with CCD enabled.
+#
+# Fast balls fall onto a thin plate and two fast kinematic blades sweep through resting balls.
+# Only the blade with CCD enabled should push its balls along. The balls have no CCD of their own
+# and the blades move 3 units per step, so a blade without CCD jumps from 1 below the balls to 2
+# above them without ever touching them.
+
+
+export var count := 20
+export var speed := 100.0
+export var blade_speed := 180.0
+export var duration := 0.5
+
+var falling_balls := []
+var blades := []
+var time := 0.0
+
+
+func _ready():
+	var space := get_viewport().world.space
+	PhysicsServer.call("space_set_max_ccd_substeps", space, 4)
+	PhysicsServer.call("space_set_ccd_prediction_distance", space, 0.1)
+
+	var plate := StaticBody.new()
+	plate.add_child(_thin_box())
+	add_child(plate)
+	for i in count:
+		var b := _ball(Vector3(i * 1.5, 10, 0))
+		b.continuous_cd = true
+		b.linear_velocity = Vector3(0, -speed, 0)
+		falling_balls.push_back(b)
+
+	for ccd in [true, false]:
+		var blade := KinematicBody.new()
+		blade.add_child(_thin_box())
+		blade.translation = Vector3(0, 10, 20 if ccd else 40)
+		PhysicsServer.body_set_enable_continuous_collision_detection(blade.get_rid(), ccd)
+		add_child(blade)
+		var balls := []
+		for i in count:
+			var b := _ball(blade.translation + Vector3(i * 1.5, 10, 0))
+			b.gravity_scale = 0.0
+			balls.push_back(b)
+		blades.push_back([blade, balls])
+
+
+func _physics_process(delta):
+	time += delta
+	for b in blades:
+		b[0].translation.y += blade_speed * delta
+	if time < duration:
+		return
+	set_physics_process(false)
+
+	var tunneled := 0
+	for b in falling_balls:
+		if b.translation.y < 0.0:
+			tunneled += 1
+	print("Plate: %d of %d balls tunneled" % [tunneled, count])
+	var failed := tunneled
+	for b in blades:
+		tunneled = 0
+		for ball in b[1]:
+			if ball.translation.y < b[0].translation.y:
+				tunneled += 1
+		var ccd: bool = PhysicsServer.body_is_continuous_collision_detection_enabled(b[0].get_rid())
+		print("Blade with CCD %s: %d of %d balls tunneled" % ["on" if ccd else "off", tunneled, count])
+		if ccd:
+			failed += tunneled
+	get_tree().quit(failed)
+
+
+func _ball(position: Vector3) -> RigidBody:
+	var shape := CollisionShape.new()
+	shape.shape = SphereShape.new()
+	shape.shape.radius = 0.5
+	var b := RigidBody.new()
+	b.translation = position
+	b.add_child(shape)
+	add_child(b)
+	return b
+
+
+func _thin_box() -> CollisionShape:
+	var shape := CollisionShape.new()
+	shape.shape = BoxShape.new()
+	shape.shape.extents = Vector3(50, 0.005, 5)
+	return shape
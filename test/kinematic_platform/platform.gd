@@ -0,0 +1,53 @@
+extends Node
+
+# A kinematic platform is moved with its transform and should carry the box on top of it along.
+# Afterwards the platform is teleported away sideways, which should not throw the box sideways.
+
+
+export var speed := 2.0
+export var duration := 2.0
+export var teleport_offset := Vector3(0, 0, 10)
+
+var platform: KinematicBody
+var box: RigidBody
+var time := 0.0
+
+
+func _ready():
+	platform = KinematicBody.new()
+	platform.add_child(_box_shape(Vector3(5, 0.5, 5)))
+	add_child(platform)
+
+	box = RigidBody.new()
+	box.translation = Vector3(0, 1, 0)
+	box.can_sleep = false
+	box.add_child(_box_shape(Vector3(0.5, 0.5, 0.5)))
+	add_child(box)
+
+
+func _physics_process(delta):
+	time += delta
+	if time < duration:
+		# Give the box some time to settle first
+		if time > 0.5:
+			platform.translation.x += speed * delta
+		return
+	set_physics_process(false)
+
+	print("Platform moved %f, box moved %f" % [platform.translation.x, box.translation.x])
+	print("Platform velocity: %s" % PhysicsServer.body_get_direct_state(platform.get_rid()).linear_velocity)
+
+	var transform := platform.global_transform
+	transform.origin += teleport_offset
+	PhysicsServer.call("body_teleport", platform.get_rid(), transform)
+	print("Platform transform after teleport: %s" % PhysicsServer.body_get_state(platform.get_rid(), PhysicsServer.BODY_STATE_TRANSFORM).origin)
+	yield(get_tree(), "physics_frame")
+	yield(get_tree(), "physics_frame")
+	print("Box velocity after teleport: %s" % box.linear_velocity)
+
+
+func _box_shape(extents: Vector3) -> CollisionShape:
+	var shape := CollisionShape.new()
+	shape.shape = BoxShape.new()
+	shape.shape.extents = extents
+	return shape